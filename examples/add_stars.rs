@@ -1,6 +1,6 @@
 use frankenstein::client_ureq::Bot;
 use frankenstein::methods::{AnswerPreCheckoutQueryParams, GetUpdatesParams, SendInvoiceParams};
-use frankenstein::payments::LabeledPrice;
+use frankenstein::payments::{Currency, LabeledPrice};
 use frankenstein::types::AllowedUpdate;
 use frankenstein::updates::UpdateContent;
 use frankenstein::TelegramApi;
@@ -19,7 +19,7 @@ fn main() {
         .title("Send stars".to_string())
         .description("Get stars for bot".to_string())
         .payload("gift_purchase".to_string())
-        .currency("XTR".to_string())
+        .currency(Currency::Xtr)
         .prices(vec![LabeledPrice {
             label: "amount".to_string(),
             amount: number_of_stars,
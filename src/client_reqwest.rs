@@ -1,11 +1,24 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use bon::Builder;
 
-use crate::input_file::InputFile;
+use crate::input_file::{mime_type, sanitize_file_name, InputFile};
+use crate::methods::{ExportChatInviteLinkParams, RevokeChatInviteLinkParams};
+use crate::response::MethodResponse;
+use crate::stickers::Sticker;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::trait_async::join_all;
 use crate::trait_async::AsyncTelegramApi;
+use crate::ttl_cache::{KeyedCache, TtlCache, FORUM_TOPIC_ICON_STICKERS_TTL};
+use crate::types::{ChatId, ChatInviteLink};
 use crate::Error;
 
 /// Asynchronous [`AsyncTelegramApi`] implementation with [`reqwest`]
+///
+/// Requests are driven by the returned futures only. Dropping one, e.g. on shutdown or on a
+/// timeout, aborts the request including a file upload in progress.
 #[derive(Debug, Clone, Builder)]
 #[must_use = "Bot needs to be used in order to be useful"]
 pub struct Bot {
@@ -14,6 +27,53 @@ pub struct Bot {
 
     #[builder(default = default_client())]
     pub client: reqwest::Client,
+
+    /// Shared between clones of the `Bot`.
+    #[builder(skip)]
+    counters: Arc<Counters>,
+
+    /// Shared between clones of the `Bot`.
+    #[builder(skip = Arc::new(TtlCache::new(FORUM_TOPIC_ICON_STICKERS_TTL)))]
+    forum_topic_icon_stickers: Arc<TtlCache<Vec<Sticker>>>,
+
+    /// Shared between clones of the `Bot`.
+    #[builder(skip = Arc::new(KeyedCache::new()))]
+    primary_invite_links: Arc<KeyedCache<ChatId, String>>,
+}
+
+/// Snapshot of the request counters of a [`Bot`], see [`Bot::metrics`].
+///
+/// The `Bot` never retries a request, so there is no retry counter. A retry by the caller is
+/// counted as another request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Metrics {
+    /// Requests which were sent but did not finish yet.
+    pub in_flight: u64,
+    /// Requests which were started since the `Bot` was created.
+    pub total_requests: u64,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    in_flight: AtomicU64,
+    total_requests: AtomicU64,
+}
+
+/// Counts a request as in flight until dropped, which also covers cancelled requests.
+struct InFlight<'a>(&'a Counters);
+
+impl<'a> InFlight<'a> {
+    fn start(counters: &'a Counters) -> Self {
+        counters.total_requests.fetch_add(1, Ordering::Relaxed);
+        counters.in_flight.fetch_add(1, Ordering::Relaxed);
+        Self(counters)
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
 }
 
 fn default_client() -> reqwest::Client {
@@ -38,6 +98,56 @@ impl Bot {
         Self::builder().api_url(api_url).build()
     }
 
+    /// Current request counters of this `Bot` and all of its clones.
+    #[must_use]
+    pub fn metrics(&self) -> Metrics {
+        Metrics {
+            in_flight: self.counters.in_flight.load(Ordering::Relaxed),
+            total_requests: self.counters.total_requests.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Cached result of [`get_forum_topic_icon_stickers`](AsyncTelegramApi::get_forum_topic_icon_stickers).
+    ///
+    /// The stickers are fetched on the first call and reused for a day afterwards.
+    /// Use [`invalidate_forum_topic_icon_stickers`](Self::invalidate_forum_topic_icon_stickers)
+    /// to fetch them again on the next call.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn forum_topic_icon_stickers(&self) -> Result<Vec<Sticker>, Error> {
+        if let Some(stickers) = self.forum_topic_icon_stickers.get() {
+            return Ok(stickers);
+        }
+        let stickers = self.get_forum_topic_icon_stickers().await?.result;
+        self.forum_topic_icon_stickers.set(stickers.clone());
+        Ok(stickers)
+    }
+
+    pub fn invalidate_forum_topic_icon_stickers(&self) {
+        self.forum_topic_icon_stickers.clear();
+    }
+
+    /// The primary invite link of the chat, only exported on the first call for `chat_id`.
+    ///
+    /// Exporting a link revokes the previous primary link, so repeated calls of
+    /// [`export_chat_invite_link`](AsyncTelegramApi::export_chat_invite_link) break links which were
+    /// already shared. Exporting or revoking the link through this `Bot` updates the cache. The
+    /// cache is keyed by `chat_id` as given, a chat id and a username of the same chat are cached
+    /// separately.
+    pub async fn primary_invite_link(&self, chat_id: ChatId) -> Result<String, Error> {
+        if let Some(invite_link) = self.primary_invite_links.get(&chat_id) {
+            return Ok(invite_link);
+        }
+        let params = ExportChatInviteLinkParams::builder()
+            .chat_id(chat_id)
+            .build();
+        Ok(self.export_chat_invite_link(&params).await?.result)
+    }
+
+    /// Forget the cached primary invite link, e.g. after it was changed by another administrator.
+    pub fn invalidate_primary_invite_link(&self, chat_id: &ChatId) {
+        self.primary_invite_links.remove(chat_id);
+    }
+
     async fn decode_response<Output>(response: reqwest::Response) -> Result<Output, Error>
     where
         Output: serde::de::DeserializeOwned,
@@ -65,6 +175,26 @@ impl From<reqwest::Error> for Error {
 impl AsyncTelegramApi for Bot {
     type Error = Error;
 
+    async fn export_chat_invite_link(
+        &self,
+        params: &ExportChatInviteLinkParams,
+    ) -> Result<MethodResponse<String>, Error> {
+        let response: MethodResponse<String> =
+            self.request("exportChatInviteLink", Some(params)).await?;
+        self.primary_invite_links
+            .insert(params.chat_id.clone(), response.result.clone());
+        Ok(response)
+    }
+
+    async fn revoke_chat_invite_link(
+        &self,
+        params: &RevokeChatInviteLinkParams,
+    ) -> Result<MethodResponse<ChatInviteLink>, Error> {
+        let response = self.request("revokeChatInviteLink", Some(params)).await?;
+        self.primary_invite_links.remove_value(&params.invite_link);
+        Ok(response)
+    }
+
     async fn request<Params, Output>(
         &self,
         method: &str,
@@ -74,6 +204,7 @@ impl AsyncTelegramApi for Bot {
         Params: serde::ser::Serialize + std::fmt::Debug + std::marker::Send,
         Output: serde::de::DeserializeOwned,
     {
+        let _in_flight = InFlight::start(&self.counters);
         let url = format!("{}/{method}", self.api_url);
         let mut prepared_request = self
             .client
@@ -103,6 +234,8 @@ impl AsyncTelegramApi for Bot {
             use reqwest::multipart;
             use serde_json::Value;
 
+            let _in_flight = InFlight::start(&self.counters);
+
             let json_string = crate::json::encode(&params)?;
             let json_struct: serde_json::Map<String, Value> =
                 serde_json::from_str(&json_string).unwrap();
@@ -118,26 +251,47 @@ impl AsyncTelegramApi for Bot {
                 }
             }
 
-            for (parameter_name, input_file) in files {
-                let part = match input_file {
-                    InputFile::Path(file_path) => {
-                        let file = tokio::fs::File::open(&file_path)
-                            .await
-                            .map_err(Error::ReadFile)?;
-                        let file_name =
-                            file_path.file_name().unwrap().to_string_lossy().to_string();
-                        multipart::Part::stream(file).file_name(file_name)
-                    }
-                    InputFile::Memory { file_name, data } => {
-                        let len = data.len() as u64;
-                        multipart::Part::stream_with_length(data, len).file_name(file_name)
-                    }
-                };
+            // The files of e.g. a media group are opened concurrently and streamed while the body
+            // is sent. Files are only accessed through `tokio::fs` so they don't block the runtime.
+            let parts = join_all(
+                files
+                    .into_iter()
+                    .map(|(parameter_name, input_file)| async move {
+                        let (part, file_name) = match input_file {
+                            InputFile::Path(file_path) => {
+                                let file = tokio::fs::File::open(&file_path)
+                                    .await
+                                    .map_err(Error::ReadFile)?;
+                                let file_name = sanitize_file_name(
+                                    &file_path.file_name().unwrap().to_string_lossy(),
+                                );
+                                (multipart::Part::stream(file), file_name)
+                            }
+                            InputFile::Memory { file_name, data } => {
+                                let len = data.len() as u64;
+                                let part = multipart::Part::stream_with_length(data, len);
+                                (part, sanitize_file_name(&file_name))
+                            }
+                        };
+                        let part = match mime_type(&file_name) {
+                            Some(mime_type) => part.mime_str(mime_type)?,
+                            None => part,
+                        };
+                        Ok::<_, Error>((parameter_name, part.file_name(file_name)))
+                    })
+                    .collect(),
+            )
+            .await;
+            for part in parts {
+                let (parameter_name, part) = part?;
                 form = form.part(parameter_name, part);
             }
 
             let url = format!("{}/{method}", self.api_url);
 
+            // The files are streamed by this future, don't spawn tasks here as dropping the
+            // future has to stop the upload. An open which already started on tokio's blocking
+            // pool still finishes, the file is closed right away then.
             let response = self.client.post(url).multipart(form).send().await?;
             Self::decode_response(response).await
         }
@@ -151,6 +305,7 @@ impl AsyncTelegramApi for Bot {
 mod tests {
     use super::*;
     use crate::methods::SendMessageParams;
+    use crate::trait_async::AsyncTelegramApiExt;
 
     #[tokio::test]
     async fn async_send_message_success() {
@@ -201,4 +356,309 @@ mod tests {
         assert_eq!(error.parameters, None);
         assert!(!error.ok);
     }
+
+    #[tokio::test]
+    async fn metrics_count_requests() {
+        let response_string = "{\"ok\":true,\"result\":true}";
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/close")
+            .with_status(200)
+            .with_body(response_string)
+            .expect(3)
+            .create_async()
+            .await;
+        let api = Bot::new_url(server.url());
+        let clone = api.clone();
+
+        assert_eq!(api.metrics(), Metrics::default());
+        for _ in 0..3 {
+            clone.close().await.unwrap();
+        }
+        mock.assert();
+        drop(server);
+
+        assert_eq!(
+            api.metrics(),
+            Metrics {
+                in_flight: 0,
+                total_requests: 3,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn chat_snapshot_calls_both_methods() {
+        let mut server = mockito::Server::new_async().await;
+        let chat = server
+            .mock("POST", "/getChat")
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":{\"id\":-100,\"type\":\"supergroup\",\"title\":\"Group\",\"accepted_gift_types\":{\"unlimited_gifts\":true,\"limited_gifts\":true,\"unique_gifts\":true,\"premium_subscription\":true}}}")
+            .create_async()
+            .await;
+        let count = server
+            .mock("POST", "/getChatMemberCount")
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":42}")
+            .create_async()
+            .await;
+        let api = Bot::new_url(server.url());
+
+        let (chat_info, member_count) = api
+            .chat_snapshot(crate::types::ChatId::Integer(-100))
+            .await
+            .unwrap();
+        chat.assert();
+        count.assert();
+        drop(server);
+
+        assert_eq!(chat_info.id, -100);
+        assert_eq!(member_count, 42);
+        assert_eq!(api.metrics().total_requests, 2);
+    }
+
+    #[tokio::test]
+    async fn primary_invite_link_is_exported_once() {
+        let mut server = mockito::Server::new_async().await;
+        let export = server
+            .mock("POST", "/exportChatInviteLink")
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":\"https://t.me/+primary\"}")
+            .expect(3)
+            .create_async()
+            .await;
+        let revoke = server
+            .mock("POST", "/revokeChatInviteLink")
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":{\"invite_link\":\"https://t.me/+primary\",\"creator\":{\"id\":1,\"is_bot\":true,\"first_name\":\"Bot\"},\"creates_join_request\":false,\"is_primary\":true,\"is_revoked\":true}}")
+            .create_async()
+            .await;
+        let api = Bot::new_url(server.url());
+        let chat_id = ChatId::Integer(-100);
+
+        let link = api.primary_invite_link(chat_id.clone()).await.unwrap();
+        assert_eq!(link, "https://t.me/+primary");
+        assert_eq!(
+            api.clone()
+                .primary_invite_link(chat_id.clone())
+                .await
+                .unwrap(),
+            link
+        );
+
+        api.invalidate_primary_invite_link(&chat_id);
+        let link = api.primary_invite_link(chat_id.clone()).await.unwrap();
+
+        let params = RevokeChatInviteLinkParams::builder()
+            .chat_id(chat_id.clone())
+            .invite_link(link)
+            .build();
+        api.revoke_chat_invite_link(&params).await.unwrap();
+        api.primary_invite_link(chat_id).await.unwrap();
+        export.assert();
+        revoke.assert();
+    }
+
+    #[tokio::test]
+    async fn forum_topic_icon_stickers_are_cached() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/getForumTopicIconStickers")
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":[]}")
+            .expect(2)
+            .create_async()
+            .await;
+        let api = Bot::new_url(server.url());
+
+        assert!(api.forum_topic_icon_stickers().await.unwrap().is_empty());
+        assert!(api
+            .clone()
+            .forum_topic_icon_stickers()
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(api.metrics().total_requests, 1);
+
+        api.invalidate_forum_topic_icon_stickers();
+        api.forum_topic_icon_stickers().await.unwrap();
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn dropping_upload_future_stops_sending() {
+        use tokio::io::AsyncReadExt;
+
+        const FILE_SIZE: usize = 16 * 1024 * 1024;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let api = Bot::new_url(format!("http://{}", listener.local_addr().unwrap()));
+        let params = crate::methods::SendDocumentParams::builder()
+            .chat_id(crate::types::ChatId::Integer(1))
+            .document(InputFile::Memory {
+                file_name: "big.bin".to_string(),
+                data: vec![0; FILE_SIZE].into(),
+            })
+            .build();
+
+        let upload = api.send_document(&params);
+        let server = async {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            // Let the client fill the socket buffers, then stop reading
+            let mut buffer = vec![0; 64 * 1024];
+            socket.read_exact(&mut buffer).await.unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            socket
+        };
+        let mut socket = tokio::select! {
+            _ = upload => panic!("the upload can't finish without a response"),
+            socket = server => socket,
+        };
+        // The upload future is dropped here, the rest of the body must not be sent anymore
+
+        let mut buffer = vec![0; 64 * 1024];
+        let mut received = 0;
+        let read_remaining = async {
+            loop {
+                match socket.read(&mut buffer).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(read) => received += read,
+                }
+            }
+        };
+        tokio::time::timeout(std::time::Duration::from_secs(5), read_remaining)
+            .await
+            .expect("connection should be closed after the future was dropped");
+        assert!(received < FILE_SIZE);
+        assert_eq!(api.metrics().in_flight, 0);
+    }
+
+    #[tokio::test]
+    async fn upload_parts_have_a_content_type() {
+        use crate::methods::SendStickerParams;
+
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/sendSticker")
+            .match_body(mockito::Matcher::Regex(
+                r#"filename="animated.tgs"\r\n[Cc]ontent-[Tt]ype: application/x-tgsticker\r\n"#
+                    .to_string(),
+            ))
+            .with_status(200)
+            .with_body(
+                "{\"ok\":true,\"result\":{\"message_id\":1,\"date\":0,\"chat\":{\"id\":1,\"type\":\"private\"}}}",
+            )
+            .create_async()
+            .await;
+        let api = Bot::new_url(server.url());
+
+        let params = SendStickerParams::builder()
+            .chat_id(crate::types::ChatId::Integer(1))
+            .sticker(InputFile::memory("animated.tgs", b"sticker".to_vec()))
+            .build();
+        api.send_sticker(&params).await.unwrap();
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn media_group_attaches_every_file() {
+        use crate::input_media::{InputMediaPhoto, MediaGroupInputMedia};
+        use crate::methods::SendMediaGroupParams;
+
+        let dir =
+            std::env::temp_dir().join(format!("frankenstein-media-group-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut media = Vec::new();
+        let mut matchers = Vec::new();
+        for index in 0..10 {
+            let path = dir.join(format!("photo{index}.jpg"));
+            std::fs::write(&path, format!("content of photo {index}")).unwrap();
+            media.push(MediaGroupInputMedia::Photo(
+                InputMediaPhoto::builder()
+                    .media(InputFile::Path(path))
+                    .build(),
+            ));
+            matchers.push(mockito::Matcher::Regex(format!(
+                r#"name="file{index}"; filename="photo{index}.jpg"\r\n(?:[^\r]*\r\n)*\r\ncontent of photo {index}\r\n"#
+            )));
+            matchers.push(mockito::Matcher::Regex(format!(r#"attach://file{index}""#)));
+        }
+
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/sendMediaGroup")
+            .match_body(mockito::Matcher::AllOf(matchers))
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":[]}")
+            .create_async()
+            .await;
+        let api = Bot::new_url(server.url());
+
+        let params = SendMediaGroupParams::builder()
+            .chat_id(crate::types::ChatId::Integer(1))
+            .media(media)
+            .build();
+        let response = api.send_media_group(&params).await;
+        std::fs::remove_dir_all(&dir).unwrap();
+        mock.assert();
+        assert!(response.unwrap().result.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "current_thread")]
+    async fn path_upload_does_not_block_the_runtime() {
+        use tokio::io::AsyncWriteExt;
+
+        use crate::methods::SendDocumentParams;
+
+        // Opening a FIFO for reading blocks until a writer opens it. The writer below runs on the
+        // same single threaded runtime, so a blocking open or read would never let it run.
+        let path = std::env::temp_dir().join(format!("frankenstein-fifo-{}", std::process::id()));
+        let status = std::process::Command::new("mkfifo")
+            .arg(&path)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        // Unblocks a blocking implementation after a while so the test fails instead of hanging.
+        let watchdog_path = path.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_secs(30));
+            drop(std::fs::OpenOptions::new().write(true).open(watchdog_path));
+        });
+
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/sendDocument")
+            .match_body(mockito::Matcher::Regex(
+                r#"filename="frankenstein-fifo-\d+"\r\n(?:[^\r]*\r\n)*\r\ncontent written later\r\n"#
+                    .to_string(),
+            ))
+            .with_status(200)
+            .with_body(
+                "{\"ok\":true,\"result\":{\"message_id\":1,\"date\":0,\"chat\":{\"id\":1,\"type\":\"private\"}}}",
+            )
+            .create_async()
+            .await;
+        let api = Bot::new_url(server.url());
+
+        let writer_path = path.clone();
+        let writer = async move {
+            let mut fifo = tokio::fs::OpenOptions::new()
+                .write(true)
+                .open(writer_path)
+                .await
+                .unwrap();
+            fifo.write_all(b"content written later").await.unwrap();
+            fifo.flush().await.unwrap();
+        };
+        let params = SendDocumentParams::builder()
+            .chat_id(crate::types::ChatId::Integer(1))
+            .document(path.clone())
+            .build();
+        let (response, ()) = tokio::join!(api.send_document(&params), writer);
+        std::fs::remove_file(&path).unwrap();
+        mock.assert();
+        assert_eq!(response.unwrap().result.message_id, 1);
+    }
 }
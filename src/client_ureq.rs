@@ -1,12 +1,18 @@
 use std::io::Cursor;
+use std::sync::Arc;
 use std::time::Duration;
 
 use bon::Builder;
-use multipart::client::lazy::Multipart;
 use serde_json::Value;
 
-use crate::input_file::InputFile;
+use crate::form_data::{random_boundary, FormData};
+use crate::input_file::{mime_type, sanitize_file_name, InputFile};
+use crate::methods::{ExportChatInviteLinkParams, RevokeChatInviteLinkParams};
+use crate::response::MethodResponse;
+use crate::stickers::Sticker;
 use crate::trait_sync::TelegramApi;
+use crate::ttl_cache::{KeyedCache, TtlCache, FORUM_TOPIC_ICON_STICKERS_TTL};
+use crate::types::{ChatId, ChatInviteLink};
 use crate::Error;
 
 /// Synchronous [`TelegramApi`] implementation with [`ureq`].
@@ -18,6 +24,29 @@ pub struct Bot {
 
     #[builder(default = default_agent())]
     pub request_agent: ureq::Agent,
+
+    /// Generates the boundary of `multipart/form-data` uploads, random by default.
+    ///
+    /// A fixed boundary makes the request body predictable in tests. Set it with
+    /// [`BotBuilder::multipart_boundary`].
+    #[builder(default = random_boundary)]
+    multipart_boundary: fn() -> String,
+
+    /// Shared between clones of the `Bot`.
+    #[builder(skip = Arc::new(TtlCache::new(FORUM_TOPIC_ICON_STICKERS_TTL)))]
+    forum_topic_icon_stickers: Arc<TtlCache<Vec<Sticker>>>,
+
+    /// Shared between clones of the `Bot`.
+    #[builder(skip = Arc::new(KeyedCache::new()))]
+    primary_invite_links: Arc<KeyedCache<ChatId, String>>,
+}
+
+/// Prefers the crate's table of Telegram file types over `mime_guess`, which doesn't know e.g.
+/// `.tgs` stickers.
+fn part_mime_type(file_name: &str) -> mime_guess::mime::Mime {
+    mime_type(file_name)
+        .and_then(|mime_type| mime_type.parse().ok())
+        .unwrap_or_else(|| mime_guess::from_path(file_name).first_or_octet_stream())
 }
 
 fn default_agent() -> ureq::Agent {
@@ -40,6 +69,46 @@ impl Bot {
         Self::builder().api_url(api_url).build()
     }
 
+    /// Cached result of [`get_forum_topic_icon_stickers`](TelegramApi::get_forum_topic_icon_stickers).
+    ///
+    /// The stickers are fetched on the first call and reused for a day afterwards.
+    /// Use [`invalidate_forum_topic_icon_stickers`](Self::invalidate_forum_topic_icon_stickers)
+    /// to fetch them again on the next call.
+    pub fn forum_topic_icon_stickers(&self) -> Result<Vec<Sticker>, Error> {
+        if let Some(stickers) = self.forum_topic_icon_stickers.get() {
+            return Ok(stickers);
+        }
+        let stickers = self.get_forum_topic_icon_stickers()?.result;
+        self.forum_topic_icon_stickers.set(stickers.clone());
+        Ok(stickers)
+    }
+
+    pub fn invalidate_forum_topic_icon_stickers(&self) {
+        self.forum_topic_icon_stickers.clear();
+    }
+
+    /// The primary invite link of the chat, only exported on the first call for `chat_id`.
+    ///
+    /// Exporting a link revokes the previous primary link, so repeated calls of
+    /// [`export_chat_invite_link`](TelegramApi::export_chat_invite_link) break links which were
+    /// already shared. Exporting or revoking the link through this `Bot` updates the cache. The
+    /// cache is keyed by `chat_id` as given, a chat id and a username of the same chat are cached
+    /// separately.
+    pub fn primary_invite_link(&self, chat_id: ChatId) -> Result<String, Error> {
+        if let Some(invite_link) = self.primary_invite_links.get(&chat_id) {
+            return Ok(invite_link);
+        }
+        let params = ExportChatInviteLinkParams::builder()
+            .chat_id(chat_id)
+            .build();
+        Ok(self.export_chat_invite_link(&params)?.result)
+    }
+
+    /// Forget the cached primary invite link, e.g. after it was changed by another administrator.
+    pub fn invalidate_primary_invite_link(&self, chat_id: &ChatId) {
+        self.primary_invite_links.remove(chat_id);
+    }
+
     fn decode_response<Output>(
         response: ureq::http::response::Response<ureq::Body>,
     ) -> Result<Output, Error>
@@ -60,6 +129,26 @@ impl Bot {
 impl TelegramApi for Bot {
     type Error = Error;
 
+    fn export_chat_invite_link(
+        &self,
+        params: &ExportChatInviteLinkParams,
+    ) -> Result<MethodResponse<String>, Error> {
+        let response: MethodResponse<String> =
+            self.request("exportChatInviteLink", Some(params))?;
+        self.primary_invite_links
+            .insert(params.chat_id.clone(), response.result.clone());
+        Ok(response)
+    }
+
+    fn revoke_chat_invite_link(
+        &self,
+        params: &RevokeChatInviteLinkParams,
+    ) -> Result<MethodResponse<ChatInviteLink>, Error> {
+        let response = self.request("revokeChatInviteLink", Some(params))?;
+        self.primary_invite_links.remove_value(&params.invite_link);
+        Ok(response)
+    }
+
     fn request<Params, Output>(&self, method: &str, params: Option<Params>) -> Result<Output, Error>
     where
         Params: serde::ser::Serialize + std::fmt::Debug,
@@ -97,13 +186,13 @@ impl TelegramApi for Bot {
             serde_json::from_str(&json_string).unwrap();
         let file_keys: Vec<String> = files.iter().map(|(key, _)| key.clone()).collect();
 
-        let mut form = Multipart::new();
+        let mut form = FormData::new((self.multipart_boundary)());
         for (key, val) in json_struct {
             if !file_keys.iter().any(|candidate| candidate == &key) {
                 match val {
-                    Value::String(val) => form.add_text(key, val),
-                    other => form.add_text(key, other.to_string()),
-                };
+                    Value::String(val) => form.add_text(&key, &val),
+                    other => form.add_text(&key, &other.to_string()),
+                }
             }
         }
 
@@ -112,29 +201,27 @@ impl TelegramApi for Bot {
                 InputFile::Path(file_path) => {
                     let name = parameter_name;
                     let file = std::fs::File::open(&file_path).map_err(Error::ReadFile)?;
-                    let file_name = file_path
-                        .file_name()
-                        .unwrap()
-                        .to_string_lossy()
-                        .into_owned();
-                    let file_extension = file_path
-                        .extension()
-                        .and_then(std::ffi::OsStr::to_str)
-                        .unwrap_or("");
-                    let mime = mime_guess::from_ext(file_extension).first_or_octet_stream();
-                    form.add_stream(name, file, Some(file_name), Some(mime));
+                    let file_name =
+                        sanitize_file_name(&file_path.file_name().unwrap().to_string_lossy());
+                    let mime = part_mime_type(&file_name);
+                    form.add_stream(&name, file, &file_name, mime.as_ref());
                 }
                 InputFile::Memory { file_name, data } => {
                     let name = parameter_name;
-                    let mime = mime_guess::from_path(&file_name).first_or_octet_stream();
+                    let mime = part_mime_type(&file_name);
                     let cursor = Cursor::new(data);
-                    form.add_stream(name, cursor, Some(file_name), Some(mime));
+                    form.add_stream(
+                        &name,
+                        cursor,
+                        &sanitize_file_name(&file_name),
+                        mime.as_ref(),
+                    );
                 }
             }
         }
 
         let url = format!("{}/{method}", self.api_url);
-        let mut form_data = form.prepare().unwrap();
+        let mut form_data = form.finish();
         let response = self
             .request_agent
             .post(&url)
@@ -150,27 +237,33 @@ impl TelegramApi for Bot {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::StateError;
     use crate::inline_mode::{InlineQueryResult, InlineQueryResultVenue};
-    use crate::input_media::{InputMediaPhoto, MediaGroupInputMedia};
+    use crate::input_media::{
+        InputMediaAnimation, InputMediaPhoto, InputMediaVideo, InputPaidMediaPhoto,
+        MediaGroupInputMedia,
+    };
     use crate::methods::{
         AnswerCallbackQueryParams, AnswerInlineQueryParams, BanChatMemberParams, CopyMessageParams,
-        CreateChatInviteLinkParams, DeleteChatPhotoParams, DeleteChatStickerSetParams,
-        DeleteMessageParams, DeleteMyCommandsParams, DeleteWebhookParams, EditChatInviteLinkParams,
-        EditMessageCaptionParams, EditMessageLiveLocationParams, EditMessageMediaParams,
-        EditMessageTextParams, ExportChatInviteLinkParams, ForwardMessageParams,
-        GetChatAdministratorsParams, GetChatMemberCountParams, GetChatMemberParams, GetFileParams,
-        GetMyCommandsParams, GetStickerSetParams, GetUpdatesParams, GetUserProfilePhotosParams,
-        LeaveChatParams, PinChatMessageParams, PromoteChatMemberParams, RestrictChatMemberParams,
+        CopyMessagesParams, CreateChatInviteLinkParams, DeleteChatPhotoParams,
+        DeleteChatStickerSetParams, DeleteMessageParams, DeleteMyCommandsParams,
+        DeleteWebhookParams, EditChatInviteLinkParams, EditMessageCaptionParams,
+        EditMessageLiveLocationParams, EditMessageMediaParams, EditMessageTextParams,
+        ExportChatInviteLinkParams, ForwardMessageParams, GetChatAdministratorsParams,
+        GetChatMemberCountParams, GetChatMemberParams, GetFileParams, GetMyCommandsParams,
+        GetStickerSetParams, GetUpdatesParams, GetUserProfilePhotosParams, LeaveChatParams,
+        PinChatMessageParams, PromoteChatMemberParams, RestrictChatMemberParams,
         RevokeChatInviteLinkParams, SendAnimationParams, SendAudioParams, SendChatActionParams,
         SendContactParams, SendDiceParams, SendDocumentParams, SendLocationParams,
-        SendMediaGroupParams, SendMessageParams, SendPhotoParams, SendPollParams,
-        SendStickerParams, SendVenueParams, SendVideoNoteParams, SendVideoParams, SendVoiceParams,
-        SetChatAdministratorCustomTitleParams, SetChatDescriptionParams, SetChatPermissionsParams,
-        SetChatPhotoParams, SetChatStickerSetParams, SetChatTitleParams, SetMyCommandsParams,
-        SetWebhookParams, StopMessageLiveLocationParams, StopPollParams, UnbanChatMemberParams,
-        UnpinChatMessageParams,
+        SendMediaGroupParams, SendMessageParams, SendPaidMediaParams, SendPhotoParams,
+        SendPollParams, SendStickerParams, SendVenueParams, SendVideoNoteParams, SendVideoParams,
+        SendVoiceParams, SetChatAdministratorCustomTitleParams, SetChatDescriptionParams,
+        SetChatPermissionsParams, SetChatPhotoParams, SetChatStickerSetParams, SetChatTitleParams,
+        SetMyCommandsParams, SetWebhookParams, StopMessageLiveLocationParams, StopPollParams,
+        UnbanChatMemberParams, UnpinChatMessageParams,
     };
     use crate::test_json::assert_json_str;
+    use crate::trait_sync::TelegramApiExt;
     use crate::types::{
         AllowedUpdate, BotCommand, BotCommandScope, BotCommandScopeChat, ChatAction, ChatId,
         ChatPermissions, InputPollOption,
@@ -841,6 +934,599 @@ mod tests {
         assert_json_str(&response, response_string);
     }
 
+    #[test]
+    fn set_commands_for_sends_scope() {
+        let response_string = "{\"ok\":true,\"result\":true}";
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/setMyCommands")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "commands": [{"command": "meow", "description": "mewo"}],
+                "scope": {"type": "chat_administrators", "chat_id": 275808073},
+            })))
+            .with_status(200)
+            .with_body(response_string)
+            .create();
+        let api = Bot::new_url(server.url());
+        let commands = vec![BotCommand::builder()
+            .command("meow")
+            .description("mewo")
+            .build()];
+        let response = api
+            .set_commands_for(BotCommandScope::chat_administrators(275808073), commands)
+            .unwrap();
+        mock.assert();
+        assert_json_str(&response, response_string);
+    }
+
+    #[test]
+    fn delete_commands_for_sends_scope_and_language() {
+        let response_string = "{\"ok\":true,\"result\":true}";
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/deleteMyCommands")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "scope": {"type": "chat", "chat_id": 275808073},
+                "language_code": "es",
+            })))
+            .with_status(200)
+            .with_body(response_string)
+            .create();
+        let api = Bot::new_url(server.url());
+        let response = api
+            .delete_commands_for(BotCommandScope::chat(275808073), Some("es"))
+            .unwrap();
+        mock.assert();
+        assert_json_str(&response, response_string);
+    }
+
+    #[test]
+    fn approve_join_requests_continues_after_failure() {
+        let mut server = mockito::Server::new();
+        let mut mocks = Vec::new();
+        for user_id in [1, 2, 3] {
+            let (status, body) = if user_id == 2 {
+                (
+                    400,
+                    "{\"ok\":false,\"description\":\"Bad Request: HIDE_REQUESTER_MISSING\",\"error_code\":400}",
+                )
+            } else {
+                (200, "{\"ok\":true,\"result\":true}")
+            };
+            mocks.push(
+                server
+                    .mock("POST", "/approveChatJoinRequest")
+                    .match_body(mockito::Matcher::Json(serde_json::json!({
+                        "chat_id": -100,
+                        "user_id": user_id,
+                    })))
+                    .with_status(status)
+                    .with_body(body)
+                    .create(),
+            );
+        }
+        let api = Bot::new_url(server.url());
+
+        let results = api.approve_join_requests(ChatId::Integer(-100), &[1, 2, 3], Duration::ZERO);
+        for mock in mocks {
+            mock.assert();
+        }
+        let user_ids: Vec<u64> = results.iter().map(|(user_id, _)| *user_id).collect();
+        assert_eq!(user_ids, [1, 2, 3]);
+        assert!(matches!(results[0].1, Ok(true)));
+        assert!(matches!(results[2].1, Ok(true)));
+        let error = match &results[1].1 {
+            Err(Error::Api(error)) => error,
+            other => panic!("API error expected: {other:?}"),
+        };
+        assert_eq!(error.description, "Bad Request: HIDE_REQUESTER_MISSING");
+    }
+
+    #[test]
+    fn convert_gifts_to_stars_reports_each_gift() {
+        let mut server = mockito::Server::new();
+        let mut mocks = Vec::new();
+        for gift_id in ["a", "b", "c"] {
+            let (status, body) = if gift_id == "b" {
+                (
+                    400,
+                    "{\"ok\":false,\"description\":\"Bad Request: STARGIFT_CONVERT_TOO_OLD\",\"error_code\":400}",
+                )
+            } else {
+                (200, "{\"ok\":true,\"result\":true}")
+            };
+            mocks.push(
+                server
+                    .mock("POST", "/convertGiftToStars")
+                    .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                        "business_connection_id": "connection",
+                        "owner_gift_id": gift_id,
+                    })))
+                    .with_status(status)
+                    .with_body(body)
+                    .expect(if gift_id == "c" { 1 } else { 2 })
+                    .create(),
+            );
+        }
+        let api = Bot::new_url(server.url());
+        let gift_ids = ["a", "b", "c"].map(str::to_string);
+
+        let results = api.convert_gifts_to_stars("connection", &gift_ids, false);
+        let converted: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(converted, ["a", "b", "c"]);
+        assert!(matches!(results[0].1, Ok(true)));
+        assert!(matches!(&results[1].1, Err(Error::Api(error)) if error.error_code == 400));
+        assert!(matches!(results[2].1, Ok(true)));
+
+        let results = api.convert_gifts_to_stars("connection", &gift_ids, true);
+        assert_eq!(results.len(), 2);
+        assert!(results[1].1.is_err());
+        for mock in mocks {
+            mock.assert();
+        }
+    }
+
+    #[test]
+    fn all_business_account_gifts_follows_next_offset() {
+        let mut server = mockito::Server::new();
+        let gift = |owned_gift_id: &str| {
+            serde_json::json!({
+                "type": "regular",
+                "owned_gift_id": owned_gift_id,
+                "send_date": 1700000000,
+                "gift": {
+                    "id": "bear",
+                    "star_count": 15,
+                    "sticker": {
+                        "file_id": "file",
+                        "file_unique_id": "unique",
+                        "type": "regular",
+                        "width": 512,
+                        "height": 512,
+                        "is_animated": false,
+                        "is_video": false
+                    }
+                }
+            })
+        };
+        let pages = [
+            (None, vec![gift("a"), gift("b")], "2"),
+            (Some("2"), vec![gift("c"), gift("d")], "4"),
+            // Repeating the offset ends the paging like an empty one.
+            (Some("4"), vec![gift("e")], "4"),
+        ];
+        let mocks: Vec<_> = pages
+            .into_iter()
+            .map(|(offset, gifts, next_offset)| {
+                let mut expected = serde_json::json!({
+                    "business_connection_id": "connection",
+                    "exclude_unique": true,
+                    "limit": 2
+                });
+                if let Some(offset) = offset {
+                    expected["offset"] = offset.into();
+                }
+                let body = serde_json::json!({
+                    "ok": true,
+                    "result": {"total_count": 5, "gifts": gifts, "next_offset": next_offset}
+                });
+                server
+                    .mock("POST", "/getBusinessAccountGifts")
+                    .match_body(mockito::Matcher::Json(expected))
+                    .with_status(200)
+                    .with_body(body.to_string())
+                    .create()
+            })
+            .collect();
+        let api = Bot::new_url(server.url());
+        let params = crate::methods::GetBusinessAccountGiftsParams::builder()
+            .business_connection_id("connection")
+            .limit(2)
+            .build()
+            .regular_only();
+
+        let gifts = api.all_business_account_gifts(&params).unwrap();
+        for mock in mocks {
+            mock.assert();
+        }
+        let ids: Vec<_> = gifts
+            .iter()
+            .map(|gift| match gift {
+                crate::gifts::OwnedGift::Regular(gift) => gift.owned_gift_id.as_deref().unwrap(),
+                crate::gifts::OwnedGift::Unique(_) => panic!("regular gift expected"),
+            })
+            .collect();
+        assert_eq!(ids, ["a", "b", "c", "d", "e"]);
+    }
+
+    #[test]
+    fn copy_messages_chunked_keeps_remove_caption() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/copyMessages")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "chat_id": 1,
+                "from_chat_id": 2,
+                "remove_caption": true,
+            })))
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":[{\"message_id\":7}]}")
+            .expect(2)
+            .create();
+        let api = Bot::new_url(server.url());
+        let params =
+            CopyMessagesParams::without_captions(2, 1, (1..=150).rev().chain([7]).collect());
+        let sent = api.copy_messages_chunked(&params).unwrap();
+        mock.assert();
+        assert_eq!(sent.len(), 2);
+    }
+
+    #[test]
+    fn forward_messages_chunked_sorts_and_combines_results() {
+        let mut server = mockito::Server::new();
+        let first = server
+            .mock("POST", "/forwardMessages")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "message_ids": (1..=100).collect::<Vec<i32>>(),
+            })))
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":[{\"message_id\":1000},{\"message_id\":1001}]}")
+            .create();
+        let second = server
+            .mock("POST", "/forwardMessages")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "chat_id": 1,
+                "from_chat_id": 2,
+                "message_ids": (101..=150).collect::<Vec<i32>>(),
+            })))
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":[{\"message_id\":2000}]}")
+            .create();
+        let api = Bot::new_url(server.url());
+        let message_ids: Vec<i32> = (1..=150).rev().chain([1, 150]).collect();
+        let sent = api
+            .forward_messages_chunked(ChatId::Integer(2), ChatId::Integer(1), &message_ids)
+            .unwrap();
+        first.assert();
+        second.assert();
+        let sent: Vec<i32> = sent.iter().map(|id| id.message_id).collect();
+        assert_eq!(sent, [1000, 1001, 2000]);
+    }
+
+    #[test]
+    fn delete_business_messages_chunked_splits_ids() {
+        let response_string = "{\"ok\":true,\"result\":true}";
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/deleteBusinessMessages")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "business_connection_id": "connection",
+            })))
+            .with_status(200)
+            .with_body(response_string)
+            .expect(2)
+            .create();
+        let api = Bot::new_url(server.url());
+        let message_ids: Vec<i32> = (1..=150).collect();
+        let responses = api
+            .delete_business_messages_chunked("connection", &message_ids)
+            .unwrap();
+        mock.assert();
+        assert_eq!(responses.len(), 2);
+    }
+
+    #[test]
+    fn mark_read_calls_read_business_message() {
+        let response_string = "{\"ok\":true,\"result\":true}";
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/readBusinessMessage")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "business_connection_id": "connection",
+                "chat_id": 275808073,
+                "message_id": 42,
+            })))
+            .with_status(200)
+            .with_body(response_string)
+            .create();
+        let api = Bot::new_url(server.url());
+        let response = api.mark_read("connection", 275808073, 42).unwrap();
+        mock.assert();
+        assert!(response.result);
+    }
+
+    #[test]
+    fn star_transactions_between_stops_after_range() {
+        let page = |first_date: u64| {
+            let transactions: Vec<_> = (0..100)
+                .map(|index| {
+                    serde_json::json!({
+                        "id": format!("{}", first_date + index * 10),
+                        "amount": 1,
+                        "date": first_date + index * 10,
+                    })
+                })
+                .collect();
+            serde_json::json!({"ok": true, "result": {"transactions": transactions}}).to_string()
+        };
+
+        let mut server = mockito::Server::new();
+        let first = server
+            .mock("POST", "/getStarTransactions")
+            .match_body(mockito::Matcher::Json(
+                serde_json::json!({"offset": 0, "limit": 100}),
+            ))
+            .with_status(200)
+            .with_body(page(0))
+            .create();
+        let second = server
+            .mock("POST", "/getStarTransactions")
+            .match_body(mockito::Matcher::Json(
+                serde_json::json!({"offset": 100, "limit": 100}),
+            ))
+            .with_status(200)
+            .with_body(page(1000))
+            .create();
+        let third = server
+            .mock("POST", "/getStarTransactions")
+            .match_body(mockito::Matcher::Json(
+                serde_json::json!({"offset": 200, "limit": 100}),
+            ))
+            .with_status(200)
+            .with_body(page(2000))
+            .expect(0)
+            .create();
+        let api = Bot::new_url(server.url());
+
+        let transactions = api.star_transactions_between(500, 1200).unwrap();
+        first.assert();
+        second.assert();
+        third.assert();
+
+        assert_eq!(transactions.len(), 71);
+        assert_eq!(transactions.first().unwrap().date, 500);
+        assert_eq!(transactions.last().unwrap().date, 1200);
+    }
+
+    fn webhook_mocks(server: &mut mockito::Server, reported_url: &str) -> [mockito::Mock; 3] {
+        let delete = server
+            .mock("POST", "/deleteWebhook")
+            .match_body(mockito::Matcher::Json(
+                serde_json::json!({"drop_pending_updates": true}),
+            ))
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":true}")
+            .create();
+        let set = server
+            .mock("POST", "/setWebhook")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"url": "https://example.com/hook"}),
+            ))
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":true}")
+            .create();
+        let info = server
+            .mock("POST", "/getWebhookInfo")
+            .with_status(200)
+            .with_body(
+                serde_json::json!({
+                    "ok": true,
+                    "result": {
+                        "url": reported_url,
+                        "has_custom_certificate": false,
+                        "pending_update_count": 0
+                    }
+                })
+                .to_string(),
+            )
+            .create();
+        [delete, set, info]
+    }
+
+    #[test]
+    fn switch_to_webhook_verifies_url() {
+        let mut server = mockito::Server::new();
+        let mocks = webhook_mocks(&mut server, "https://example.com/hook");
+        let api = Bot::new_url(server.url());
+        let params = SetWebhookParams::builder()
+            .url("https://example.com/hook")
+            .drop_pending_updates(true)
+            .build();
+
+        let info = api.switch_to_webhook(&params).unwrap();
+        for mock in mocks {
+            mock.assert();
+        }
+        assert_eq!(info.result.url, "https://example.com/hook");
+    }
+
+    #[test]
+    fn switch_to_webhook_fails_on_url_mismatch() {
+        let mut server = mockito::Server::new();
+        let mocks = webhook_mocks(&mut server, "");
+        let api = Bot::new_url(server.url());
+        let params = SetWebhookParams::builder()
+            .url("https://example.com/hook")
+            .drop_pending_updates(true)
+            .build();
+
+        let error = api.switch_to_webhook(&params).unwrap_err();
+        for mock in mocks {
+            mock.assert();
+        }
+        assert!(matches!(
+            error,
+            Error::State(StateError::WebhookMismatch { expected, actual })
+                if expected == "https://example.com/hook" && actual.is_empty()
+        ));
+    }
+
+    fn webhook_info_mock(server: &mut mockito::Server, url: &str) -> mockito::Mock {
+        server
+            .mock("POST", "/getWebhookInfo")
+            .with_status(200)
+            .with_body(
+                serde_json::json!({
+                    "ok": true,
+                    "result": {
+                        "url": url,
+                        "has_custom_certificate": false,
+                        "pending_update_count": 0
+                    }
+                })
+                .to_string(),
+            )
+            .create()
+    }
+
+    #[test]
+    fn ensure_polling_possible_rejects_active_webhook() {
+        let mut server = mockito::Server::new();
+        let mock = webhook_info_mock(&mut server, "https://example.com/hook");
+        let api = Bot::new_url(server.url());
+
+        let error = api.ensure_polling_possible().unwrap_err();
+        mock.assert();
+        assert!(matches!(
+            error,
+            Error::State(StateError::WebhookActive { url }) if url == "https://example.com/hook"
+        ));
+    }
+
+    #[test]
+    fn ensure_polling_possible_accepts_missing_webhook() {
+        let mut server = mockito::Server::new();
+        let mock = webhook_info_mock(&mut server, "");
+        let api = Bot::new_url(server.url());
+
+        api.ensure_polling_possible().unwrap();
+        mock.assert();
+    }
+
+    #[test]
+    fn checked_star_transfer_rejects_amount_over_balance() {
+        let mut server = mockito::Server::new();
+        let balance = server
+            .mock("POST", "/getBusinessAccountStarBalance")
+            .match_body(mockito::Matcher::Json(
+                serde_json::json!({"business_connection_id": "connection"}),
+            ))
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":{\"amount\":50}}")
+            .expect(2)
+            .create();
+        let transfer = server
+            .mock("POST", "/transferBusinessAccountStars")
+            .match_body(mockito::Matcher::Json(
+                serde_json::json!({"business_connection_id": "connection", "star_count": 50}),
+            ))
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":true}")
+            .expect(1)
+            .create();
+        let api = Bot::new_url(server.url());
+        let params = crate::methods::TransferBusinessAccountStarsParams::builder()
+            .business_connection_id("connection")
+            .star_count(51)
+            .build();
+
+        let error = api
+            .transfer_business_account_stars_checked(&params)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::State(StateError::InsufficientStarBalance {
+                balance: 50,
+                requested: 51
+            })
+        ));
+
+        let params = crate::methods::TransferBusinessAccountStarsParams {
+            star_count: 50,
+            ..params
+        };
+        assert!(
+            api.transfer_business_account_stars_checked(&params)
+                .unwrap()
+                .result
+        );
+        balance.assert();
+        transfer.assert();
+    }
+
+    #[test]
+    fn clear_reactions_sends_empty_reaction_list() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/setMessageReaction")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "chat_id": 1,
+                "message_id": 2,
+                "reaction": []
+            })))
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":true}")
+            .create();
+        let api = Bot::new_url(server.url());
+
+        let response = api.clear_reactions(ChatId::Integer(1), 2).unwrap();
+        mock.assert();
+        assert!(response.result);
+    }
+
+    #[test]
+    fn forum_topic_icon_stickers_are_cached() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/getForumTopicIconStickers")
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":[]}")
+            .expect(2)
+            .create();
+        let api = Bot::new_url(server.url());
+
+        assert!(api.forum_topic_icon_stickers().unwrap().is_empty());
+        assert!(api.clone().forum_topic_icon_stickers().unwrap().is_empty());
+
+        api.invalidate_forum_topic_icon_stickers();
+        api.forum_topic_icon_stickers().unwrap();
+        mock.assert();
+    }
+
+    #[test]
+    fn primary_invite_link_is_exported_once() {
+        let mut server = mockito::Server::new();
+        let export = server
+            .mock("POST", "/exportChatInviteLink")
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":\"https://t.me/+primary\"}")
+            .expect(2)
+            .create();
+        let revoke = server
+            .mock("POST", "/revokeChatInviteLink")
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":{\"invite_link\":\"https://t.me/+primary\",\"creator\":{\"id\":1,\"is_bot\":true,\"first_name\":\"Bot\"},\"creates_join_request\":false,\"is_primary\":true,\"is_revoked\":true}}")
+            .create();
+        let api = Bot::new_url(server.url());
+        let chat_id = ChatId::Integer(-100);
+
+        let link = api.primary_invite_link(chat_id.clone()).unwrap();
+        assert_eq!(link, "https://t.me/+primary");
+        assert_eq!(
+            api.clone().primary_invite_link(chat_id.clone()).unwrap(),
+            link
+        );
+
+        let params = RevokeChatInviteLinkParams::builder()
+            .chat_id(chat_id.clone())
+            .invite_link(link)
+            .build();
+        api.revoke_chat_invite_link(&params).unwrap();
+        api.primary_invite_link(chat_id).unwrap();
+        export.assert();
+        revoke.assert();
+    }
+
     #[test]
     fn delete_my_commands_success() {
         let response_string = "{\"ok\":true,\"result\":true}";
@@ -990,6 +1676,235 @@ mod tests {
         assert_json_str(&response, response_string);
     }
 
+    fn multipart_mock(
+        server: &mut mockito::Server,
+        method: &str,
+        attach_names: &[&str],
+    ) -> mockito::Mock {
+        let matchers = attach_names
+            .iter()
+            .flat_map(|name| {
+                [
+                    mockito::Matcher::Regex(format!("name=\"{name}\"")),
+                    mockito::Matcher::Regex(format!("attach://{name}\"")),
+                ]
+            })
+            .collect();
+        server
+            .mock("POST", format!("/{method}").as_str())
+            .match_body(mockito::Matcher::AllOf(matchers))
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":true}")
+            .create()
+    }
+
+    #[test]
+    fn edit_message_media_attaches_media_and_thumbnail_separately() {
+        let mut server = mockito::Server::new();
+        let mock = multipart_mock(
+            &mut server,
+            "editMessageMedia",
+            &["video_media", "video_thumbnail"],
+        );
+        let api = Bot::new_url(server.url());
+        // Same file name on purpose, only the attach names have to differ
+        let video = InputMediaVideo::builder()
+            .media(InputFile::memory("same.bin", b"video".to_vec()))
+            .thumbnail(InputFile::memory("same.bin", b"thumbnail".to_vec()))
+            .build();
+        let params = EditMessageMediaParams::builder()
+            .chat_id(1)
+            .message_id(2)
+            .media(video)
+            .build();
+        api.edit_message_media(&params).unwrap();
+        mock.assert();
+    }
+
+    #[test]
+    fn edit_message_media_animation_names_do_not_clash() {
+        let mut server = mockito::Server::new();
+        let mock = multipart_mock(
+            &mut server,
+            "editMessageMedia",
+            &["animation_media", "animation_thumbnail"],
+        );
+        let api = Bot::new_url(server.url());
+        let animation = InputMediaAnimation::builder()
+            .media(InputFile::memory("same.bin", b"animation".to_vec()))
+            .thumbnail(InputFile::memory("same.bin", b"thumbnail".to_vec()))
+            .build();
+        let params = EditMessageMediaParams::builder()
+            .inline_message_id("inline")
+            .media(animation)
+            .build();
+        api.edit_message_media(&params).unwrap();
+        mock.assert();
+    }
+
+    #[test]
+    fn send_media_group_attach_names_are_unique() {
+        let mut server = mockito::Server::new();
+        let mock = multipart_mock(
+            &mut server,
+            "sendMediaGroup",
+            &["file0", "file1", "file2", "file3"],
+        );
+        let api = Bot::new_url(server.url());
+        let video = |media: &'static [u8], thumbnail: &'static [u8]| {
+            InputMediaVideo::builder()
+                .media(InputFile::memory("same.bin", media))
+                .thumbnail(InputFile::memory("same.bin", thumbnail))
+                .build()
+        };
+        // Equal contents would be uploaded once, so every file differs
+        let params = SendMediaGroupParams::builder()
+            .chat_id(1)
+            .media(vec![
+                MediaGroupInputMedia::Video(video(b"video 1", b"thumbnail 1")),
+                MediaGroupInputMedia::Video(video(b"video 2", b"thumbnail 2")),
+            ])
+            .build();
+        // The mock answers with `true` instead of messages, only the request matters here
+        let _ = api.send_media_group(&params);
+        mock.assert();
+    }
+
+    #[test]
+    fn multipart_body_with_fixed_boundary() {
+        let mut server = mockito::Server::new();
+        let body = concat!(
+            "--boundary\r\n",
+            "Content-Disposition: form-data; name=\"caption\"\r\n\r\n",
+            "hi\r\n",
+            "--boundary\r\n",
+            "Content-Disposition: form-data; name=\"chat_id\"\r\n\r\n",
+            "1\r\n",
+            "--boundary\r\n",
+            "Content-Disposition: form-data; name=\"document\"; filename=\"a.png\"\r\n",
+            "Content-Type: image/png\r\n\r\n",
+            "png\r\n",
+            "--boundary--\r\n",
+        );
+        let mock = server
+            .mock("POST", "/sendDocument")
+            .match_header("content-type", "multipart/form-data; boundary=boundary")
+            .match_body(body)
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":true}")
+            .create();
+        let api = Bot::builder()
+            .api_url(server.url())
+            .multipart_boundary(|| "boundary".to_string())
+            .build();
+
+        let response: MethodResponse<bool> = api
+            .request_with_form_data(
+                "sendDocument",
+                serde_json::json!({"chat_id": 1, "caption": "hi"}),
+                vec![(
+                    "document".to_string(),
+                    InputFile::memory("a.png", b"png".to_vec()),
+                )],
+            )
+            .unwrap();
+        mock.assert();
+        assert!(response.result);
+    }
+
+    #[test]
+    fn send_media_group_uploads_shared_thumbnail_once() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/sendMediaGroup")
+            .match_body(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::Regex(r#"name="file2""#.to_string()),
+                mockito::Matcher::Regex("attach://file1.*attach://file1".to_string()),
+            ]))
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":true}")
+            .create();
+        let api = Bot::new_url(server.url());
+        let thumbnail = InputFile::memory("thumb.jpg", b"shared thumbnail".to_vec());
+        let first = InputMediaVideo::builder()
+            .media(InputFile::memory("first.mp4", b"first".to_vec()))
+            .thumbnail(thumbnail.clone())
+            .build();
+        let second = InputMediaVideo::builder()
+            .media(InputFile::memory("second.mp4", b"second".to_vec()))
+            .thumbnail(thumbnail)
+            .build();
+        let params = SendMediaGroupParams::builder()
+            .chat_id(1)
+            .media(vec![first.into(), second.into()])
+            .build();
+        // The mock answers with `true` instead of messages, only the request matters here
+        let _ = api.send_media_group(&params);
+        mock.assert();
+    }
+
+    #[test]
+    fn send_paid_media_uploads_memory_photo() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/sendPaidMedia")
+            .match_body(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::Regex(r#"name="file0"; filename="photo.png""#.to_string()),
+                mockito::Matcher::Regex("attach://file0".to_string()),
+                mockito::Matcher::Regex("paid photo bytes".to_string()),
+            ]))
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":{\"message_id\":1,\"date\":1700000000,\"chat\":{\"id\":1,\"type\":\"private\",\"first_name\":\"User\"}}}")
+            .create();
+        let api = Bot::new_url(server.url());
+        let photo = InputPaidMediaPhoto::builder()
+            .media(InputFile::memory("photo.png", b"paid photo bytes".to_vec()))
+            .build();
+        let params = SendPaidMediaParams::new(1, 10).add_media(photo);
+        let response = api.send_paid_media(&params).unwrap();
+        mock.assert();
+        assert_eq!(response.result.message_id, 1);
+    }
+
+    #[test]
+    fn request_raw_get_me() {
+        let response_string = "{\"ok\":true,\"result\":{\"id\":1276618370,\"is_bot\":true,\"first_name\":\"test_el_bot\",\"username\":\"el_mon_test_bot\"}}";
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/getMe")
+            .with_status(200)
+            .with_body(response_string)
+            .create();
+        let api = Bot::new_url(server.url());
+        let response = api.request_raw("getMe", None).unwrap();
+        mock.assert();
+        assert_eq!(response["ok"], true);
+        assert_eq!(response["result"]["id"], 1276618370);
+    }
+
+    #[test]
+    fn chat_snapshot_calls_both_methods() {
+        let mut server = mockito::Server::new();
+        let chat = server
+            .mock("POST", "/getChat")
+            .match_body(mockito::Matcher::Json(serde_json::json!({"chat_id": -100})))
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":{\"id\":-100,\"type\":\"supergroup\",\"title\":\"Group\",\"accepted_gift_types\":{\"unlimited_gifts\":true,\"limited_gifts\":true,\"unique_gifts\":true,\"premium_subscription\":true}}}")
+            .create();
+        let count = server
+            .mock("POST", "/getChatMemberCount")
+            .match_body(mockito::Matcher::Json(serde_json::json!({"chat_id": -100})))
+            .with_status(200)
+            .with_body("{\"ok\":true,\"result\":42}")
+            .create();
+        let api = Bot::new_url(server.url());
+        let (chat_info, member_count) = api.chat_snapshot(ChatId::Integer(-100)).unwrap();
+        chat.assert();
+        count.assert();
+        assert_eq!(chat_info.title.as_deref(), Some("Group"));
+        assert_eq!(member_count, 42);
+    }
+
     #[test]
     fn returns_decode_error_if_response_can_not_be_decoded() {
         let response_string = "{hey this json is invalid}";
@@ -19,6 +19,12 @@ pub enum Error {
         input: String,
     },
 
+    #[error(transparent)]
+    Validation(#[from] ValidationError),
+
+    #[error(transparent)]
+    State(#[from] StateError),
+
     #[error("Read File Error: {0}")]
     ReadFile(#[source] std::io::Error),
 
@@ -35,6 +41,41 @@ pub enum Error {
     HttpUreq(#[from] ureq::Error),
 }
 
+/// Parameters were rejected locally before sending them to Telegram.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Validation Error on {field}: {reason}")]
+pub struct ValidationError {
+    /// Name of the offending parameter.
+    pub field: &'static str,
+    pub reason: String,
+}
+
+impl ValidationError {
+    pub(crate) fn new<R: Into<String>>(field: &'static str, reason: R) -> Self {
+        Self {
+            field,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Telegram reported a state which doesn't match what a convenience method expects.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum StateError {
+    /// `getWebhookInfo` reports another webhook than the one which was just set.
+    #[error("State Error: expected the webhook {expected}, Telegram reports {actual:?}")]
+    WebhookMismatch { expected: String, actual: String },
+
+    /// A webhook is set, so updates can't be received with `getUpdates`.
+    #[error("State Error: the webhook {url} is active, delete it before polling")]
+    WebhookActive { url: String },
+
+    /// The business account has fewer Stars than should be transferred.
+    #[error("State Error: the balance of {balance} stars is too low to transfer {requested}")]
+    InsufficientStarBalance { balance: i32, requested: u32 },
+}
+
 impl Error {
     #[cfg(test)]
     #[track_caller]
@@ -0,0 +1,233 @@
+//! In-memory cache for downloaded files, keyed by `file_id`.
+//!
+//! The `file_path` returned by `getFile` is only guaranteed to be valid for about an hour,
+//! so the cache keys on the stable `file_id` and only calls `getFile` on a cache miss.
+
+use std::collections::{HashMap, VecDeque};
+
+use bytes::Bytes;
+
+#[cfg(any(feature = "trait-sync", feature = "trait-async"))]
+use crate::methods::GetFileParams;
+#[cfg(any(feature = "trait-sync", feature = "trait-async"))]
+use crate::types::File;
+
+/// Least recently used cache of file contents with a total size cap in bytes.
+///
+/// When an insert would exceed the cap, the least recently used entries are evicted first.
+/// Files bigger than the cap are never cached.
+#[derive(Clone, Debug)]
+pub struct FileCache {
+    max_bytes: usize,
+    total_bytes: usize,
+    entries: HashMap<String, Bytes>,
+    /// `file_id`s from least to most recently used.
+    order: VecDeque<String>,
+}
+
+impl FileCache {
+    #[must_use]
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            total_bytes: 0,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    #[must_use]
+    pub const fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    /// Sum of the sizes of all cached files.
+    #[must_use]
+    pub const fn total_bytes(&self) -> usize {
+        self.total_bytes
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains(&self, file_id: &str) -> bool {
+        self.entries.contains_key(file_id)
+    }
+
+    /// Returns the cached contents and marks the entry as recently used.
+    pub fn get(&mut self, file_id: &str) -> Option<Bytes> {
+        let data = self.entries.get(file_id)?.clone();
+        self.touch(file_id);
+        Some(data)
+    }
+
+    /// Caches the contents of `file_id`, evicting least recently used entries when needed.
+    ///
+    /// Returns `false` when the data is bigger than the cache and was not stored.
+    pub fn insert<D: Into<Bytes>>(&mut self, file_id: impl Into<String>, data: D) -> bool {
+        let file_id = file_id.into();
+        let data = data.into();
+        self.remove(&file_id);
+        if data.len() > self.max_bytes {
+            return false;
+        }
+        while self.total_bytes + data.len() > self.max_bytes {
+            let Some(oldest) = self.order.pop_front() else {
+                break;
+            };
+            if let Some(evicted) = self.entries.remove(&oldest) {
+                self.total_bytes -= evicted.len();
+            }
+        }
+        self.total_bytes += data.len();
+        self.order.push_back(file_id.clone());
+        self.entries.insert(file_id, data);
+        true
+    }
+
+    pub fn remove(&mut self, file_id: &str) -> Option<Bytes> {
+        let data = self.entries.remove(file_id)?;
+        self.total_bytes -= data.len();
+        self.order.retain(|id| id != file_id);
+        Some(data)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+        self.total_bytes = 0;
+    }
+
+    fn touch(&mut self, file_id: &str) {
+        if let Some(index) = self.order.iter().position(|id| id == file_id) {
+            if let Some(id) = self.order.remove(index) {
+                self.order.push_back(id);
+            }
+        }
+    }
+
+    /// Returns the contents of `file_id`, downloading them on a cache miss.
+    ///
+    /// On a miss `getFile` is called and `download` gets the resulting [`File`] to fetch the
+    /// contents from its `file_path`.
+    #[cfg(feature = "trait-sync")]
+    pub fn download_file<Api, Download>(
+        &mut self,
+        api: &Api,
+        file_id: &str,
+        download: Download,
+    ) -> Result<Bytes, Api::Error>
+    where
+        Api: crate::TelegramApi,
+        Download: FnOnce(&File) -> Result<Vec<u8>, Api::Error>,
+    {
+        if let Some(data) = self.get(file_id) {
+            return Ok(data);
+        }
+        let params = GetFileParams::builder().file_id(file_id).build();
+        let file = api.get_file(&params)?.result;
+        let data = Bytes::from(download(&file)?);
+        self.insert(file_id, data.clone());
+        Ok(data)
+    }
+
+    /// Async version of [`download_file`](Self::download_file).
+    #[cfg(feature = "trait-async")]
+    pub async fn download_file_async<Api, Download, Fut>(
+        &mut self,
+        api: &Api,
+        file_id: &str,
+        download: Download,
+    ) -> Result<Bytes, Api::Error>
+    where
+        Api: crate::AsyncTelegramApi,
+        Download: FnOnce(File) -> Fut,
+        Fut: std::future::Future<Output = Result<Vec<u8>, Api::Error>>,
+    {
+        if let Some(data) = self.get(file_id) {
+            return Ok(data);
+        }
+        let params = GetFileParams::builder().file_id(file_id).build();
+        let file = api.get_file(&params).await?.result;
+        let data = Bytes::from(download(file).await?);
+        self.insert(file_id, data.clone());
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = FileCache::new(10);
+        assert!(cache.insert("a", vec![0; 4]));
+        assert!(cache.insert("b", vec![0; 4]));
+        assert!(cache.get("a").is_some());
+        assert!(cache.insert("c", vec![0; 4]));
+
+        assert!(cache.contains("a"));
+        assert!(!cache.contains("b"));
+        assert!(cache.contains("c"));
+        assert_eq!(cache.total_bytes(), 8);
+    }
+
+    #[test]
+    fn does_not_cache_files_bigger_than_the_cap() {
+        let mut cache = FileCache::new(4);
+        assert!(cache.insert("a", vec![0; 4]));
+        assert!(!cache.insert("b", vec![0; 5]));
+        assert!(cache.contains("a"));
+        assert!(!cache.contains("b"));
+    }
+
+    #[test]
+    fn reinserting_replaces_entry() {
+        let mut cache = FileCache::new(10);
+        cache.insert("a", vec![0; 4]);
+        cache.insert("a", vec![1; 6]);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.total_bytes(), 6);
+        assert_eq!(cache.get("a").unwrap().as_ref(), &[1; 6]);
+    }
+
+    #[cfg(feature = "client-ureq")]
+    #[test]
+    fn cached_file_is_not_downloaded_twice() {
+        use crate::client_ureq::Bot;
+
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/getFile")
+            .with_status(200)
+            .with_body(r#"{"ok":true,"result":{"file_id":"id","file_unique_id":"unique","file_size":3,"file_path":"photos/file_0.jpg"}}"#)
+            .expect(1)
+            .create();
+        let api = Bot::new_url(server.url());
+
+        let mut downloads = 0;
+        let mut cache = FileCache::new(1024);
+        for _ in 0..2 {
+            let data = cache
+                .download_file(&api, "id", |file| {
+                    downloads += 1;
+                    assert_eq!(file.file_path.as_deref(), Some("photos/file_0.jpg"));
+                    Ok(vec![1, 2, 3])
+                })
+                .unwrap();
+            assert_eq!(data.as_ref(), &[1, 2, 3]);
+        }
+
+        mock.assert();
+        assert_eq!(downloads, 1);
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::VecDeque;
+use std::io::{Cursor, Read};
+
+/// Streamed `multipart/form-data` body, the files are only read while the body is sent.
+///
+/// The `multipart` crate generates its boundary internally, so it can't be used with the boundary
+/// of [`BotBuilder::multipart_boundary`](crate::client_ureq::BotBuilder::multipart_boundary).
+pub struct FormData {
+    boundary: String,
+    parts: VecDeque<Box<dyn Read>>,
+}
+
+impl FormData {
+    pub const fn new(boundary: String) -> Self {
+        Self {
+            boundary,
+            parts: VecDeque::new(),
+        }
+    }
+
+    pub fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    pub fn add_text(&mut self, name: &str, value: &str) {
+        let part = format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{value}\r\n",
+            self.boundary,
+            escape_quoted(name)
+        );
+        self.parts.push_back(Box::new(Cursor::new(part)));
+    }
+
+    pub fn add_stream<R: Read + 'static>(
+        &mut self,
+        name: &str,
+        stream: R,
+        file_name: &str,
+        mime_type: &str,
+    ) {
+        let header = format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: {mime_type}\r\n\r\n",
+            self.boundary,
+            escape_quoted(name),
+            escape_quoted(file_name)
+        );
+        self.parts.push_back(Box::new(Cursor::new(header)));
+        self.parts.push_back(Box::new(stream));
+        self.parts.push_back(Box::new(Cursor::new("\r\n")));
+    }
+
+    /// Closes the body, reading from it afterwards yields the complete request body.
+    pub fn finish(mut self) -> Self {
+        let end = format!("--{}--\r\n", self.boundary);
+        self.parts.push_back(Box::new(Cursor::new(end)));
+        self
+    }
+}
+
+impl Read for FormData {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while let Some(part) = self.parts.front_mut() {
+            let read = part.read(buf)?;
+            if read > 0 || buf.is_empty() {
+                return Ok(read);
+            }
+            self.parts.pop_front();
+        }
+        Ok(0)
+    }
+}
+
+/// Percent-encodes `"`, CR and LF of a quoted header parameter like browsers do, see RFC 7578.
+fn escape_quoted(value: &str) -> String {
+    value
+        .replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Random boundary which is unlikely to appear in the uploaded files.
+pub fn random_boundary() -> String {
+    format!("{:032x}", crate::random::random_u128())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_parameters_are_escaped() {
+        let mut form = FormData::new("boundary".to_string());
+        form.add_text("na\"me\r\n", "value");
+        form.add_stream("file", Cursor::new("data"), "a\"b.txt", "text/plain");
+        let mut body = String::new();
+        form.finish().read_to_string(&mut body).unwrap();
+
+        assert_eq!(
+            body,
+            "--boundary\r\nContent-Disposition: form-data; name=\"na%22me%0D%0A\"\r\n\r\nvalue\r\n\
+             --boundary\r\nContent-Disposition: form-data; name=\"file\"; filename=\"a%22b.txt\"\r\nContent-Type: text/plain\r\n\r\ndata\r\n\
+             --boundary--\r\n"
+        );
+    }
+}
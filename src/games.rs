@@ -25,3 +25,70 @@ pub struct GameHighScore {
     pub user: User,
     pub score: i32,
 }
+
+/// Leaderboard helpers for the result of `getGameHighScores`.
+///
+/// Telegram returns the scores of the user and several of their neighbors, ordered by position.
+pub trait GameHighScores {
+    /// The first `n` entries by position.
+    fn top(&self, n: usize) -> Vec<&GameHighScore>;
+
+    fn rank_of(&self, user_id: u64) -> Option<u32>;
+
+    fn score_of(&self, user_id: u64) -> Option<i32>;
+}
+
+impl GameHighScores for [GameHighScore] {
+    fn top(&self, n: usize) -> Vec<&GameHighScore> {
+        let mut scores: Vec<&GameHighScore> = self.iter().collect();
+        scores.sort_by_key(|score| score.position);
+        scores.truncate(n);
+        scores
+    }
+
+    fn rank_of(&self, user_id: u64) -> Option<u32> {
+        self.iter()
+            .find(|score| score.user.id == user_id)
+            .map(|score| score.position)
+    }
+
+    fn score_of(&self, user_id: u64) -> Option<i32> {
+        self.iter()
+            .find(|score| score.user.id == user_id)
+            .map(|score| score.score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn high_score(position: u32, user_id: u64, score: i32) -> GameHighScore {
+        GameHighScore {
+            position,
+            user: User::builder()
+                .id(user_id)
+                .is_bot(false)
+                .first_name(format!("Player {user_id}"))
+                .build(),
+            score,
+        }
+    }
+
+    #[test]
+    fn leaderboard_lookups() {
+        let scores = vec![
+            high_score(3, 30, 500),
+            high_score(1, 10, 900),
+            high_score(2, 20, 700),
+        ];
+
+        let top: Vec<u64> = scores.top(2).iter().map(|score| score.user.id).collect();
+        assert_eq!(top, [10, 20]);
+        assert_eq!(scores.top(10).len(), 3);
+        assert_eq!(scores.rank_of(30), Some(3));
+        assert_eq!(scores.score_of(20), Some(700));
+        assert_eq!(scores.rank_of(40), None);
+        assert_eq!(scores.score_of(40), None);
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+use crate::error::ValidationError;
 use crate::macros::{apistruct, apply};
 use crate::stickers::Sticker;
 use crate::types::{Chat, MessageEntity, User};
@@ -15,11 +16,42 @@ pub struct Gift {
     pub publisher_chat: Option<Box<Chat>>,
 }
 
+impl Gift {
+    /// Whether a limited gift has no remaining copies left to be sent.
+    #[must_use]
+    pub const fn is_sold_out(&self) -> bool {
+        matches!(self.remaining_count, Some(0))
+    }
+}
+
 #[apply(apistruct!)]
 pub struct Gifts {
     pub gifts: Vec<Gift>,
 }
 
+impl Gifts {
+    #[must_use]
+    pub fn by_id(&self, id: &str) -> Option<&Gift> {
+        self.gifts.iter().find(|gift| gift.id == id)
+    }
+
+    /// Cheapest gift which is not sold out.
+    #[must_use]
+    pub fn cheapest(&self) -> Option<&Gift> {
+        self.gifts
+            .iter()
+            .filter(|gift| !gift.is_sold_out())
+            .min_by_key(|gift| gift.star_count)
+    }
+
+    /// Gifts which are not sold out and cost at most `stars`.
+    pub fn affordable(&self, stars: u32) -> impl Iterator<Item = &Gift> {
+        self.gifts
+            .iter()
+            .filter(move |gift| !gift.is_sold_out() && gift.star_count <= stars)
+    }
+}
+
 #[apply(apistruct!)]
 pub struct UniqueGiftModel {
     pub name: String,
@@ -102,6 +134,29 @@ pub struct AcceptedGiftTypes {
     pub premium_subscription: bool,
 }
 
+impl AcceptedGiftTypes {
+    /// Accept every type of gift.
+    pub const ALL: Self = Self {
+        unlimited_gifts: true,
+        limited_gifts: true,
+        unique_gifts: true,
+        premium_subscription: true,
+    };
+    /// Accept no gifts at all.
+    pub const NONE: Self = Self {
+        unlimited_gifts: false,
+        limited_gifts: false,
+        unique_gifts: false,
+        premium_subscription: false,
+    };
+
+    /// Whether at least one type of gift is accepted.
+    #[must_use]
+    pub const fn accepts_any(&self) -> bool {
+        self.unlimited_gifts || self.limited_gifts || self.unique_gifts || self.premium_subscription
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum OwnedGift {
@@ -143,3 +198,161 @@ pub struct OwnedGifts {
     pub gifts: Vec<OwnedGift>,
     pub next_offset: Option<String>,
 }
+
+impl OwnedGifts {
+    /// `owned_gift_id`s of the regular gifts which can still be converted to Telegram Stars.
+    #[must_use]
+    pub fn convertible_gift_ids(&self) -> Vec<String> {
+        self.gifts
+            .iter()
+            .filter_map(|gift| match gift {
+                OwnedGift::Regular(gift) if gift.convert_star_count.is_some() => {
+                    gift.owned_gift_id.clone()
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Durations of a Telegram Premium subscription `giftPremiumSubscription` accepts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PremiumMonths {
+    Three,
+    Six,
+    Twelve,
+}
+
+impl PremiumMonths {
+    #[must_use]
+    pub const fn month_count(self) -> u32 {
+        match self {
+            Self::Three => 3,
+            Self::Six => 6,
+            Self::Twelve => 12,
+        }
+    }
+
+    /// Telegram Stars the subscription of this duration costs.
+    #[must_use]
+    pub const fn star_count(self) -> u32 {
+        match self {
+            Self::Three => 1000,
+            Self::Six => 1500,
+            Self::Twelve => 2500,
+        }
+    }
+}
+
+impl TryFrom<u32> for PremiumMonths {
+    type Error = ValidationError;
+
+    fn try_from(month_count: u32) -> Result<Self, Self::Error> {
+        match month_count {
+            3 => Ok(Self::Three),
+            6 => Ok(Self::Six),
+            12 => Ok(Self::Twelve),
+            _ => Err(ValidationError::new(
+                "month_count",
+                format!("must be 3, 6 or 12, got {month_count}"),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gifts() -> Gifts {
+        let gift = |id: &str, star_count: u32, remaining_count: Option<u32>| {
+            serde_json::from_value(serde_json::json!({
+                "id": id,
+                "sticker": {
+                    "file_id": "file",
+                    "file_unique_id": "unique",
+                    "type": "regular",
+                    "width": 512,
+                    "height": 512,
+                    "is_animated": false,
+                    "is_video": false
+                },
+                "star_count": star_count,
+                "remaining_count": remaining_count
+            }))
+            .unwrap()
+        };
+        Gifts {
+            gifts: vec![
+                gift("bear", 15, None),
+                gift("rose", 25, None),
+                gift("limited", 10, Some(0)),
+                gift("cake", 50, Some(3)),
+            ],
+        }
+    }
+
+    #[test]
+    fn gift_lookup_by_id() {
+        let gifts = gifts();
+        assert_eq!(gifts.by_id("cake").unwrap().star_count, 50);
+        assert!(gifts.by_id("unknown").is_none());
+    }
+
+    #[test]
+    fn gift_budget_skips_sold_out() {
+        let gifts = gifts();
+        assert_eq!(gifts.cheapest().unwrap().id, "bear");
+
+        let affordable: Vec<_> = gifts.affordable(25).map(|gift| gift.id.as_str()).collect();
+        assert_eq!(affordable, ["bear", "rose"]);
+        assert_eq!(gifts.affordable(14).count(), 0);
+    }
+
+    #[test]
+    fn premium_months_star_costs() {
+        let costs = [
+            (PremiumMonths::Three, 3, 1000),
+            (PremiumMonths::Six, 6, 1500),
+            (PremiumMonths::Twelve, 12, 2500),
+        ];
+        for (months, month_count, star_count) in costs {
+            assert_eq!(months.month_count(), month_count);
+            assert_eq!(months.star_count(), star_count);
+            assert_eq!(PremiumMonths::try_from(month_count), Ok(months));
+        }
+        assert_eq!(PremiumMonths::try_from(1).unwrap_err().field, "month_count");
+    }
+
+    #[test]
+    fn convertible_owned_gifts() {
+        let gift = gifts().gifts.remove(0);
+        let regular = |owned_gift_id: Option<&str>, convert_star_count: Option<u32>| {
+            OwnedGift::Regular(Box::new(OwnedGiftRegular {
+                gift: gift.clone(),
+                owned_gift_id: owned_gift_id.map(str::to_string),
+                sender_user: None,
+                send_date: None,
+                text: None,
+                entities: None,
+                is_private: None,
+                is_saved: None,
+                can_be_upgraded: None,
+                was_refunded: None,
+                convert_star_count,
+                prepaid_upgrade_star_count: None,
+            }))
+        };
+        let owned = OwnedGifts {
+            total_count: 3,
+            gifts: vec![
+                regular(Some("a"), Some(10)),
+                regular(Some("b"), None),
+                regular(None, Some(10)),
+                regular(Some("c"), Some(12)),
+            ],
+            next_offset: None,
+        };
+        assert_eq!(owned.convertible_gift_ids(), ["a", "c"]);
+    }
+}
@@ -2,11 +2,13 @@
 
 #![allow(deprecated)]
 
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 
 use crate::macros::{apistruct, apply};
 use crate::parse_mode::ParseMode;
-use crate::payments::LabeledPrice;
+use crate::payments::{Currency, LabeledPrice};
 use crate::types::{
     InlineKeyboardMarkup, LinkPreviewOptions, Location, MessageEntity, User, WebAppInfo,
 };
@@ -29,6 +31,23 @@ pub struct InlineQueryResultsButton {
     pub start_parameter: Option<String>,
 }
 
+impl InlineQueryResultsButton {
+    /// Button launching the Web App, e.g. to configure the bot.
+    #[must_use]
+    pub fn web_app<T: Into<String>>(text: T, web_app: WebAppInfo) -> Self {
+        Self::builder().text(text).web_app(web_app).build()
+    }
+
+    /// Button opening a private chat with the bot, sending `/start start_parameter`.
+    #[must_use]
+    pub fn start_parameter<T: Into<String>, P: Into<String>>(text: T, start_parameter: P) -> Self {
+        Self::builder()
+            .text(text)
+            .start_parameter(start_parameter)
+            .build()
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum InlineQueryResult {
@@ -96,6 +115,46 @@ iqr_from!(Game);
 iqr_from!(Location);
 iqr_from!(Venue);
 
+/// Evaluates `$body` with `$inner` bound to the result struct of any variant.
+macro_rules! with_result {
+    ($result:expr, $inner:ident => $body:expr) => {
+        match $result {
+            InlineQueryResult::Audio(MaybeCached::Cached($inner)) => $body,
+            InlineQueryResult::Audio(MaybeCached::NotCached($inner)) => $body,
+            InlineQueryResult::Document(MaybeCached::Cached($inner)) => $body,
+            InlineQueryResult::Document(MaybeCached::NotCached($inner)) => $body,
+            InlineQueryResult::Gif(MaybeCached::Cached($inner)) => $body,
+            InlineQueryResult::Gif(MaybeCached::NotCached($inner)) => $body,
+            InlineQueryResult::Mpeg4Gif(MaybeCached::Cached($inner)) => $body,
+            InlineQueryResult::Mpeg4Gif(MaybeCached::NotCached($inner)) => $body,
+            InlineQueryResult::Photo(MaybeCached::Cached($inner)) => $body,
+            InlineQueryResult::Photo(MaybeCached::NotCached($inner)) => $body,
+            InlineQueryResult::Sticker($inner) => $body,
+            InlineQueryResult::Video(MaybeCached::Cached($inner)) => $body,
+            InlineQueryResult::Video(MaybeCached::NotCached($inner)) => $body,
+            InlineQueryResult::Voice(MaybeCached::Cached($inner)) => $body,
+            InlineQueryResult::Voice(MaybeCached::NotCached($inner)) => $body,
+            InlineQueryResult::Article($inner) => $body,
+            InlineQueryResult::Contact($inner) => $body,
+            InlineQueryResult::Game($inner) => $body,
+            InlineQueryResult::Location($inner) => $body,
+            InlineQueryResult::Venue($inner) => $body,
+        }
+    };
+}
+
+impl InlineQueryResult {
+    /// Unique identifier of the result.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        with_result!(self, result => &result.id)
+    }
+
+    pub(crate) fn id_mut(&mut self) -> &mut String {
+        with_result!(self, result => &mut result.id)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum MaybeCached<T1, T2> {
@@ -477,7 +536,8 @@ pub struct InputInvoiceMessageContent {
     pub description: String,
     pub payload: String,
     pub provider_token: Option<String>,
-    pub currency: String,
+    #[builder(into)]
+    pub currency: Currency,
     pub prices: Vec<LabeledPrice>,
     pub max_tip_amount: Option<u32>,
     pub suggested_tip_amounts: Option<Vec<u32>>,
@@ -516,3 +576,40 @@ pub struct PreparedInlineMessage {
     pub id: String,
     pub expiration_date: u64,
 }
+
+impl PreparedInlineMessage {
+    /// Whether the prepared message can no longer be used at the unix timestamp `now`.
+    #[must_use]
+    pub const fn is_expired(&self, now: u64) -> bool {
+        self.expiration_date <= now
+    }
+
+    /// Time left until the message expires at the unix timestamp `now`, zero once it expired.
+    #[must_use]
+    pub const fn time_to_live(&self, now: u64) -> Duration {
+        Duration::from_secs(self.expiration_date.saturating_sub(now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prepared_inline_message_expiry() {
+        let message = PreparedInlineMessage {
+            id: "prepared".to_string(),
+            expiration_date: 1_700_000_000,
+        };
+
+        assert!(!message.is_expired(1_699_999_000));
+        assert_eq!(
+            message.time_to_live(1_699_999_000),
+            Duration::from_secs(1000)
+        );
+
+        assert!(message.is_expired(1_700_000_000));
+        assert!(message.is_expired(1_700_000_500));
+        assert_eq!(message.time_to_live(1_700_000_500), Duration::ZERO);
+    }
+}
@@ -1,6 +1,7 @@
 //! Structs for handling and uploading files
 
 use std::fmt;
+use std::ops::RangeBounds;
 use std::path::{Path, PathBuf};
 
 use bytes::Bytes;
@@ -17,7 +18,7 @@ pub enum InputFile {
 }
 
 impl InputFile {
-    /// 以本地路径构建文件。
+    /// Upload the file at `path`.
     pub fn from_path<P>(path: P) -> Self
     where
         P: Into<PathBuf>,
@@ -25,21 +26,115 @@ impl InputFile {
         Self::Path(path.into())
     }
 
-    /// 以内存数据构建文件。
+    /// Upload data from memory under the given file name.
+    ///
+    /// The file name is cleaned up with [`sanitize_file_name`]. A `Vec<u8>` or [`Bytes`] is not
+    /// copied. Borrowed data which isn't `'static`, e.g. a `&[u8]` or `[u8; N]`, has to be
+    /// converted first with [`Bytes::copy_from_slice`] or `to_vec`.
     pub fn memory<N, D>(file_name: N, data: D) -> Self
     where
         N: Into<String>,
-        D: Into<Vec<u8>>,
+        D: Into<Bytes>,
     {
-        let bytes: Vec<u8> = data.into();
-        let data = Bytes::from(bytes);
         Self::Memory {
-            file_name: file_name.into(),
-            data,
+            file_name: sanitize_file_name(&file_name.into()),
+            data: data.into(),
+        }
+    }
+
+    /// Upload a part of `bytes`, e.g. a single frame of a larger buffer.
+    ///
+    /// The data is shared with `bytes` instead of copied. Panics when `range` is out of bounds,
+    /// like [`Bytes::slice`].
+    #[must_use]
+    pub fn memory_slice<N, R>(file_name: N, bytes: &Bytes, range: R) -> Self
+    where
+        N: Into<String>,
+        R: RangeBounds<usize>,
+    {
+        Self::memory(file_name, bytes.slice(range))
+    }
+
+    /// Upload a PNG image from memory as `image.png`.
+    #[must_use]
+    pub fn memory_png<D: Into<Bytes>>(data: D) -> Self {
+        Self::memory("image.png", data)
+    }
+
+    /// Upload a JPEG image from memory as `image.jpg`.
+    #[must_use]
+    pub fn memory_jpeg<D: Into<Bytes>>(data: D) -> Self {
+        Self::memory("image.jpg", data)
+    }
+
+    /// Upload an MP4 video from memory as `video.mp4`.
+    #[must_use]
+    pub fn memory_mp4<D: Into<Bytes>>(data: D) -> Self {
+        Self::memory("video.mp4", data)
+    }
+
+    /// File name of an in-memory file, `None` for a path.
+    #[must_use]
+    pub fn file_name(&self) -> Option<&str> {
+        match self {
+            Self::Path(_) => None,
+            Self::Memory { file_name, .. } => Some(file_name),
         }
     }
 }
 
+/// Clean up the name of an uploaded file for the `Content-Disposition` header.
+///
+/// - directories are removed, both `/` and `\` count as separator, so `../../etc/passwd` becomes
+///   `passwd`
+/// - control characters and `"` are replaced with `_`
+/// - an empty name, `.` or `..` becomes `file`
+///
+/// All other characters are kept, e.g. `my.report.2024.pdf` stays as it is.
+#[must_use]
+pub fn sanitize_file_name(file_name: &str) -> String {
+    let base_name = file_name.rsplit(['/', '\\']).next().unwrap_or_default();
+    let sanitized: String = base_name
+        .chars()
+        .map(|character| {
+            if character.is_control() || character == '"' {
+                '_'
+            } else {
+                character
+            }
+        })
+        .collect();
+    match sanitized.trim() {
+        "" | "." | ".." => "file".to_owned(),
+        _ => sanitized,
+    }
+}
+
+/// MIME type of an uploaded file from its extension, for the `Content-Type` of the multipart part.
+///
+/// Only covers the types commonly sent to Telegram. The extension is case insensitive, an unknown
+/// one returns `None`.
+#[cfg(any(feature = "client-reqwest", feature = "client-ureq"))]
+pub(crate) fn mime_type(file_name: &str) -> Option<&'static str> {
+    let (_, extension) = file_name.rsplit_once('.')?;
+    let mime_type = match extension.to_ascii_lowercase().as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "mp3" => "audio/mpeg",
+        "m4a" => "audio/mp4",
+        "ogg" | "oga" => "audio/ogg",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "tgs" => "application/x-tgsticker",
+        _ => return None,
+    };
+    Some(mime_type)
+}
+
 impl Serialize for InputFile {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -160,7 +255,7 @@ impl<'a> From<(&'a str, Vec<u8>)> for InputFile {
 
 impl<'a> From<(&'a str, &'a [u8])> for InputFile {
     fn from((file_name, data): (&'a str, &'a [u8])) -> Self {
-        Self::memory(file_name, data.to_vec())
+        Self::memory(file_name, Bytes::copy_from_slice(data))
     }
 }
 
@@ -214,16 +309,13 @@ impl<'a> From<(&'a str, &'a [u8])> for FileUpload {
 
 impl From<(String, Bytes)> for InputFile {
     fn from((file_name, data): (String, Bytes)) -> Self {
-        Self::Memory { file_name, data }
+        Self::memory(file_name, data)
     }
 }
 
 impl<'a> From<(&'a str, Bytes)> for InputFile {
     fn from((file_name, data): (&'a str, Bytes)) -> Self {
-        Self::Memory {
-            file_name: file_name.to_owned(),
-            data,
-        }
+        Self::memory(file_name, data)
     }
 }
 
@@ -241,10 +333,67 @@ impl<'a> From<(&'a str, Bytes)> for FileUpload {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[cfg(any(feature = "trait-sync", feature = "trait-async"))]
     use super::HasInputFile;
+    use super::*;
+
+    #[test]
+    fn memory_files_share_bytes() {
+        let blob = Bytes::from((0..=255).collect::<Vec<u8>>());
+
+        match InputFile::memory_slice("frame.bin", &blob, 16..32) {
+            InputFile::Memory { file_name, data } => {
+                assert_eq!(file_name, "frame.bin");
+                assert_eq!(data.as_ref(), &blob[16..32]);
+                assert_eq!(data.as_ptr(), blob[16..].as_ptr());
+            }
+            InputFile::Path(_) => panic!("expected memory variant"),
+        }
+        match InputFile::memory("blob.bin", blob.clone()) {
+            InputFile::Memory { data, .. } => assert_eq!(data.as_ptr(), blob.as_ptr()),
+            InputFile::Path(_) => panic!("expected memory variant"),
+        }
+        match InputFile::from(("../blob.bin", blob.clone())) {
+            InputFile::Memory { file_name, data } => {
+                assert_eq!(file_name, "blob.bin");
+                assert_eq!(data.as_ptr(), blob.as_ptr());
+            }
+            InputFile::Path(_) => panic!("expected memory variant"),
+        }
+    }
+
+    #[test]
+    fn memory_constructors_do_not_copy() {
+        let data = vec![7_u8; 1024];
+        let pointer = data.as_ptr();
+        match InputFile::memory_png(data) {
+            InputFile::Memory { data, .. } => assert_eq!(data.as_ptr(), pointer),
+            InputFile::Path(_) => panic!("expected memory variant"),
+        }
+
+        let downloaded = Bytes::from(vec![1_u8; 1024]);
+        let upload = FileUpload::from(("photo.jpg".to_string(), downloaded.clone()));
+        match upload {
+            FileUpload::InputFile(InputFile::Memory { data, .. }) => {
+                assert_eq!(data.as_ptr(), downloaded.as_ptr());
+            }
+            other => panic!("expected memory upload: {other:?}"),
+        }
+    }
+
+    #[cfg(any(feature = "client-reqwest", feature = "client-ureq"))]
+    #[test]
+    fn mime_type_from_extension() {
+        assert_eq!(mime_type("photo.jpg"), Some("image/jpeg"));
+        assert_eq!(mime_type("PHOTO.JPEG"), Some("image/jpeg"));
+        assert_eq!(mime_type("clip.mp4"), Some("video/mp4"));
+        assert_eq!(mime_type("sticker.webp"), Some("image/webp"));
+        assert_eq!(mime_type("animated.tgs"), Some("application/x-tgsticker"));
+        assert_eq!(mime_type("voice.ogg"), Some("audio/ogg"));
+        assert_eq!(mime_type("my.report.2024.pdf"), Some("application/pdf"));
+        assert_eq!(mime_type("notes.txt"), None);
+        assert_eq!(mime_type("Makefile"), None);
+    }
 
     #[cfg(any(feature = "trait-sync", feature = "trait-async"))]
     #[test]
@@ -271,6 +420,79 @@ mod tests {
         }
     }
 
+    #[test]
+    fn file_names_are_sanitized() {
+        assert_eq!(sanitize_file_name("../../etc/passwd"), "passwd");
+        assert_eq!(sanitize_file_name("C:\\Users\\bot\\photo.jpg"), "photo.jpg");
+        assert_eq!(sanitize_file_name("report\n.pdf"), "report_.pdf");
+        assert_eq!(sanitize_file_name("say \"hi\".txt"), "say _hi_.txt");
+        assert_eq!(
+            sanitize_file_name("my.report.2024.pdf"),
+            "my.report.2024.pdf"
+        );
+        assert_eq!(sanitize_file_name("uploads/.."), "file");
+        assert_eq!(sanitize_file_name(""), "file");
+
+        let file = InputFile::memory("../../etc/passwd", vec![1]);
+        assert_eq!(file.file_name(), Some("passwd"));
+    }
+
+    #[test]
+    fn memory_helpers_use_matching_extension() {
+        assert_eq!(
+            InputFile::memory_png(vec![0]).file_name(),
+            Some("image.png")
+        );
+        assert_eq!(
+            InputFile::memory_jpeg(vec![0]).file_name(),
+            Some("image.jpg")
+        );
+        assert_eq!(
+            InputFile::memory_mp4(vec![0]).file_name(),
+            Some("video.mp4")
+        );
+        assert_eq!(InputFile::from_path("photo.png").file_name(), None);
+    }
+
+    #[cfg(any(feature = "trait-sync", feature = "trait-async"))]
+    #[test]
+    fn replace_attach_dedup_uploads_identical_memory_files_once() {
+        let thumbnail = InputFile::memory("thumb.jpg", vec![1_u8, 2, 3]);
+        let mut uploads = [
+            Some(FileUpload::from(thumbnail.clone())),
+            Some(FileUpload::from(InputFile::memory("other.jpg", vec![4_u8]))),
+            Some(FileUpload::from(thumbnail)),
+            Some(FileUpload::from(InputFile::from_path("thumb.jpg"))),
+            Some(FileUpload::from(InputFile::from_path("thumb.jpg"))),
+        ];
+
+        let mut attachments = Attachments::default();
+        for upload in &mut uploads {
+            upload.replace_attach_dedup(&mut attachments);
+        }
+        let files = attachments.into_files();
+
+        let names: Vec<_> = files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["file0", "file1", "file2", "file3"]);
+        let attachments: Vec<_> = uploads
+            .iter()
+            .map(|upload| match upload {
+                Some(FileUpload::String(attach)) => attach.as_str(),
+                _ => panic!("every upload should be attached"),
+            })
+            .collect();
+        assert_eq!(
+            attachments,
+            [
+                "attach://file0",
+                "attach://file1",
+                "attach://file0",
+                "attach://file2",
+                "attach://file3"
+            ]
+        );
+    }
+
     #[test]
     fn memory_input_file_serializes_as_null() {
         #[derive(Serialize)]
@@ -289,12 +511,76 @@ mod tests {
 
 #[cfg(any(feature = "trait-sync", feature = "trait-async"))]
 pub(crate) trait HasInputFile {
+    fn input_file(&self) -> Option<&InputFile>;
     fn replace_attach(&mut self, name: &str) -> Option<InputFile>;
     fn replace_attach_dyn(&mut self, index: impl FnOnce() -> usize) -> Option<(String, InputFile)>;
+
+    /// Like [`replace_attach_dyn`](Self::replace_attach_dyn) but an in-memory file with the same
+    /// name and content as an already attached one reuses that attachment instead of being
+    /// uploaded again.
+    fn replace_attach_dedup(&mut self, attachments: &mut Attachments) {
+        let hash = match self.input_file() {
+            Some(InputFile::Memory { file_name, data }) => Some(memory_hash(file_name, data)),
+            _ => None,
+        };
+        let existing = hash.and_then(|hash| {
+            attachments
+                .memory_hashes
+                .iter()
+                .filter(|(other_hash, _)| *other_hash == hash)
+                .map(|&(_, index)| &attachments.files[index])
+                .find(|(_, other)| self.input_file() == Some(other))
+                .map(|(name, _)| name.clone())
+        });
+        if let Some(name) = existing {
+            self.replace_attach(&name);
+        } else if let Some(file) = self.replace_attach_dyn(|| attachments.files.len()) {
+            if let Some(hash) = hash {
+                attachments
+                    .memory_hashes
+                    .push((hash, attachments.files.len()));
+            }
+            attachments.files.push(file);
+        }
+    }
+}
+
+/// Files attached with [`HasInputFile::replace_attach_dedup`].
+///
+/// The in-memory files are hashed once when attached, so only files with the same hash are
+/// compared byte by byte.
+#[cfg(any(feature = "trait-sync", feature = "trait-async"))]
+#[derive(Default)]
+pub(crate) struct Attachments {
+    files: Vec<(String, InputFile)>,
+    memory_hashes: Vec<(u64, usize)>,
+}
+
+#[cfg(any(feature = "trait-sync", feature = "trait-async"))]
+impl Attachments {
+    pub(crate) fn into_files(self) -> Vec<(String, InputFile)> {
+        self.files
+    }
+}
+
+#[cfg(any(feature = "trait-sync", feature = "trait-async"))]
+fn memory_hash(file_name: &str, data: &Bytes) -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    (file_name, data).hash(&mut hasher);
+    hasher.finish()
 }
 
 #[cfg(any(feature = "trait-sync", feature = "trait-async"))]
 impl HasInputFile for FileUpload {
+    fn input_file(&self) -> Option<&InputFile> {
+        match self {
+            Self::InputFile(file) => Some(file),
+            Self::String(_) => None,
+        }
+    }
+
     fn replace_attach(&mut self, name: &str) -> Option<InputFile> {
         match self {
             Self::InputFile(_) => {
@@ -325,6 +611,10 @@ impl HasInputFile for FileUpload {
 
 #[cfg(any(feature = "trait-sync", feature = "trait-async"))]
 impl HasInputFile for Option<FileUpload> {
+    fn input_file(&self) -> Option<&InputFile> {
+        self.as_ref().and_then(HasInputFile::input_file)
+    }
+
     fn replace_attach(&mut self, name: &str) -> Option<InputFile> {
         match self {
             Some(FileUpload::InputFile(_)) => {
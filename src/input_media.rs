@@ -1,5 +1,7 @@
 //! API Objects related to [InputMedia](https://core.telegram.org/bots/api#inputmedia)
 
+use std::path::PathBuf;
+
 use serde::{Deserialize, Serialize};
 
 use crate::input_file::FileUpload;
@@ -151,18 +153,30 @@ pub enum InputPaidMedia {
     Video(InputPaidMediaVideo),
 }
 
+impl From<InputPaidMediaPhoto> for InputPaidMedia {
+    fn from(value: InputPaidMediaPhoto) -> Self {
+        Self::Photo(value)
+    }
+}
+
+impl From<InputPaidMediaVideo> for InputPaidMedia {
+    fn from(value: InputPaidMediaVideo) -> Self {
+        Self::Video(value)
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct InputPaidMediaPhoto {
-    pub media: String,
+    pub media: FileUpload,
 }
 
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct InputPaidMediaVideo {
-    pub media: String,
-    pub thumbnail: String,
-    pub cover: Option<String>,
+    pub media: FileUpload,
+    pub thumbnail: Option<FileUpload>,
+    pub cover: Option<FileUpload>,
     pub start_timestamp: Option<u64>,
     pub width: Option<u32>,
     pub height: Option<u32>,
@@ -196,6 +210,22 @@ pub enum InputStoryContent {
     Video(InputStoryContentVideo),
 }
 
+impl InputStoryContent {
+    /// Photo story uploaded from a local file.
+    #[must_use]
+    pub fn photo_path<P: Into<PathBuf>>(path: P) -> Self {
+        Self::Photo(InputStoryContentPhoto {
+            photo: FileUpload::from(path.into()),
+        })
+    }
+
+    /// Video story uploaded from a local file.
+    #[must_use]
+    pub fn video_path<P: Into<PathBuf>>(path: P) -> Self {
+        Self::Video(InputStoryContentVideo::builder().video(path.into()).build())
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct InputStoryContentPhoto {
@@ -1,13 +1,28 @@
 use crate::Error;
 
-/// Shortcut for [`serde_json::from_str`] with [`crate::Error`].
+/// Shortcut for [`serde_json::from_str`] with [`crate::Error`], see [`decode_slice`].
 pub fn decode<T>(string: &str) -> Result<T, Error>
 where
     T: serde::de::DeserializeOwned,
 {
-    serde_json::from_str(string).map_err(|error| Error::JsonDecode {
+    decode_slice(string.as_bytes())
+}
+
+/// Shortcut for [`serde_json::from_slice`] with [`crate::Error`].
+///
+/// With the `simd-json` feature the input is parsed with `simd_json` first. Input it fails on
+/// is parsed again with `serde_json`, so errors stay the same with both backends.
+pub fn decode_slice<T>(bytes: &[u8]) -> Result<T, Error>
+where
+    T: serde::de::DeserializeOwned,
+{
+    #[cfg(feature = "simd-json")]
+    if let Ok(value) = simd_json::serde::from_slice(&mut bytes.to_vec()) {
+        return Ok(value);
+    }
+    serde_json::from_slice(bytes).map_err(|error| Error::JsonDecode {
         source: error,
-        input: string.to_owned(),
+        input: String::from_utf8_lossy(bytes).into_owned(),
     })
 }
 
@@ -21,3 +36,31 @@ where
         input: format!("{value:?}"),
     })
 }
+
+#[cfg(all(test, feature = "simd-json"))]
+mod tests {
+    use crate::updates::Update;
+
+    #[test]
+    fn backends_parse_updates_identically() {
+        let json = r#"{
+            "update_id": 10000,
+            "message": {
+                "message_id": 1365,
+                "date": 1441645532,
+                "chat": {"id": -1001234567890, "type": "supergroup", "title": "Group"},
+                "from": {"id": 1111111, "is_bot": false, "first_name": "Jane", "language_code": "en"},
+                "text": "/start ünïcode",
+                "entities": [{"type": "bot_command", "offset": 0, "length": 6}]
+            }
+        }"#;
+        let with_serde_json: Update = serde_json::from_str(json).unwrap();
+        let with_simd_json: Update =
+            simd_json::serde::from_slice(&mut json.as_bytes().to_vec()).unwrap();
+        assert_eq!(with_simd_json, with_serde_json);
+        assert_eq!(
+            Update::from_bytes(json.as_bytes()).unwrap(),
+            with_serde_json
+        );
+    }
+}
@@ -7,18 +7,21 @@ pub use reqwest;
 #[cfg(feature = "client-ureq")]
 pub use ureq;
 
-pub use self::error::Error;
+pub use self::error::{Error, StateError, ValidationError};
 pub use self::parse_mode::ParseMode;
 #[cfg(feature = "trait-async")]
-pub use self::trait_async::AsyncTelegramApi;
+pub use self::trait_async::{AsyncTelegramApi, AsyncTelegramApiExt};
 #[cfg(feature = "trait-sync")]
-pub use self::trait_sync::TelegramApi;
+pub use self::trait_sync::{TelegramApi, TelegramApiExt};
 
 #[cfg(feature = "client-reqwest")]
 pub mod client_reqwest;
 #[cfg(feature = "client-ureq")]
 pub mod client_ureq;
 mod error;
+pub mod file_cache;
+#[cfg(feature = "client-ureq")]
+mod form_data;
 pub mod games;
 pub mod gifts;
 pub mod inline_mode;
@@ -31,6 +34,7 @@ pub mod methods;
 mod parse_mode;
 pub mod passport;
 pub mod payments;
+mod random;
 pub mod response;
 pub mod stickers;
 #[cfg(test)]
@@ -39,6 +43,8 @@ mod test_json;
 mod trait_async;
 #[cfg(feature = "trait-sync")]
 mod trait_sync;
+#[cfg(any(feature = "client-reqwest", feature = "client-ureq"))]
+mod ttl_cache;
 pub mod types;
 pub mod updates;
 
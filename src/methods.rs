@@ -1,6 +1,9 @@
 //! Parameters of [Bot API methods](https://core.telegram.org/bots/api#available-methods).
 
-use crate::gifts::AcceptedGiftTypes;
+use std::time::{Duration, SystemTime};
+
+use crate::error::ValidationError;
+use crate::gifts::{AcceptedGiftTypes, OwnedGiftUnique, PremiumMonths};
 use crate::inline_mode::{InlineQueryResult, InlineQueryResultsButton};
 use crate::input_file::{FileUpload, InputFile};
 use crate::input_media::{
@@ -9,13 +12,13 @@ use crate::input_media::{
 use crate::macros::{apistruct, apply};
 use crate::parse_mode::ParseMode;
 use crate::passport::PassportElementError;
-use crate::payments::{LabeledPrice, ShippingOption};
+use crate::payments::{Currency, LabeledPrice, ShippingOption};
 use crate::stickers::{InputSticker, MaskPosition, StickerFormat, StickerType};
 use crate::types::{
-    AllowedUpdate, BotCommand, BotCommandScope, ChatAction, ChatAdministratorRights, ChatId,
-    ChatPermissions, InlineKeyboardMarkup, InputChecklist, InputPollOption, LinkPreviewOptions,
-    MenuButton, MessageEntity, PollType, ReactionType, ReplyMarkup, ReplyParameters, StoryArea,
-    SuggestedPostParameters,
+    AllowedUpdate, BotCommand, BotCommandScope, CallbackQuery, ChatAction, ChatAdministratorRights,
+    ChatId, ChatPermissions, InlineKeyboardMarkup, InputChecklist, InputPollOption,
+    LinkPreviewOptions, MenuButton, MessageEntity, PollType, ReactionType, ReplyMarkup,
+    ReplyParameters, StoryArea, SuggestedPostParameters,
 };
 
 #[apply(apistruct!)]
@@ -91,6 +94,29 @@ pub struct ForwardMessagesParams {
     pub protect_content: Option<bool>,
 }
 
+impl ForwardMessagesParams {
+    /// Maximum number of `message_ids` Telegram accepts in one call.
+    pub const MAX_MESSAGE_IDS: usize = 100;
+
+    /// Check that 1-100 `message_ids` are given in strictly increasing order.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let count = self.message_ids.len();
+        if !(1..=Self::MAX_MESSAGE_IDS).contains(&count) {
+            return Err(ValidationError::new(
+                "message_ids",
+                format!("needs 1-{} ids, got {count}", Self::MAX_MESSAGE_IDS),
+            ));
+        }
+        if self.message_ids.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(ValidationError::new(
+                "message_ids",
+                "has to be sorted in strictly increasing order",
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct CopyMessageParams {
@@ -125,6 +151,40 @@ pub struct CopyMessagesParams {
     pub remove_caption: Option<bool>,
 }
 
+impl CopyMessagesParams {
+    /// Maximum number of `message_ids` Telegram accepts in one call.
+    pub const MAX_MESSAGE_IDS: usize = 100;
+
+    /// Params copying `message_ids` from `from_chat_id` to `chat_id` with their captions removed.
+    #[must_use]
+    pub fn without_captions<F: Into<ChatId>, T: Into<ChatId>>(
+        from_chat_id: F,
+        chat_id: T,
+        message_ids: Vec<i32>,
+    ) -> Self {
+        Self::builder()
+            .chat_id(chat_id)
+            .from_chat_id(from_chat_id)
+            .message_ids(message_ids)
+            .build()
+            .remove_captions()
+    }
+
+    /// Copy the messages with their original captions, which is the default.
+    #[must_use]
+    pub const fn keep_captions(mut self) -> Self {
+        self.remove_caption = None;
+        self
+    }
+
+    /// Strip the captions from all copied messages.
+    #[must_use]
+    pub const fn remove_captions(mut self) -> Self {
+        self.remove_caption = Some(true);
+        self
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct SendPhotoParams {
@@ -171,6 +231,25 @@ pub struct SendAudioParams {
     pub reply_markup: Option<ReplyMarkup>,
 }
 
+impl SendAudioParams {
+    /// Params sending a music track displayed with its `performer` and `title`.
+    #[must_use]
+    pub fn music<C, A, P, T>(chat_id: C, audio: A, performer: P, title: T) -> Self
+    where
+        C: Into<ChatId>,
+        A: Into<FileUpload>,
+        P: Into<String>,
+        T: Into<String>,
+    {
+        Self::builder()
+            .chat_id(chat_id)
+            .audio(audio)
+            .performer(performer)
+            .title(title)
+            .build()
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct SendDocumentParams {
@@ -289,6 +368,40 @@ pub struct SendVideoNoteParams {
     pub reply_markup: Option<ReplyMarkup>,
 }
 
+impl SendVideoNoteParams {
+    /// Longest video note in seconds.
+    pub const MAX_DURATION: u32 = 60;
+    /// Biggest diameter of a video note in pixels.
+    pub const MAX_LENGTH: u32 = 640;
+
+    /// Check the `duration` and `length` metadata before calling `sendVideoNote`.
+    ///
+    /// Uploaded video notes need a `length` as the video itself is not inspected.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(duration) = self.duration {
+            if duration > Self::MAX_DURATION {
+                return Err(ValidationError::new(
+                    "duration",
+                    format!(
+                        "can be at most {} seconds, got {duration}",
+                        Self::MAX_DURATION
+                    ),
+                ));
+            }
+        }
+        match self.length {
+            None if matches!(self.video_note, FileUpload::InputFile(_)) => Err(
+                ValidationError::new("length", "is required when uploading a video note"),
+            ),
+            Some(length) if !(1..=Self::MAX_LENGTH).contains(&length) => Err(ValidationError::new(
+                "length",
+                format!("has to be 1-{}, got {length}", Self::MAX_LENGTH),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct SendPaidMediaParams {
@@ -311,6 +424,47 @@ pub struct SendPaidMediaParams {
     pub reply_markup: Option<ReplyMarkup>,
 }
 
+impl SendPaidMediaParams {
+    /// Maximum number of media in one paid media message.
+    pub const MAX_MEDIA: usize = 10;
+
+    /// Paid media message without any media yet, see [`add_media`](Self::add_media).
+    #[must_use]
+    pub fn new<C: Into<ChatId>>(chat_id: C, star_count: u32) -> Self {
+        Self::builder()
+            .chat_id(chat_id)
+            .star_count(star_count)
+            .media(Vec::new())
+            .build()
+    }
+
+    #[must_use]
+    pub fn add_media<M: Into<InputPaidMedia>>(mut self, media: M) -> Self {
+        self.media.push(media.into());
+        self
+    }
+
+    /// Check that a price is set, 1-10 media are given and the caption formatting is unambiguous.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        exclusive_formatting(
+            "caption_entities",
+            self.parse_mode,
+            self.caption_entities.as_ref(),
+        )?;
+        if self.star_count == 0 {
+            return Err(ValidationError::new("star_count", "has to be positive"));
+        }
+        let media = self.media.len();
+        if !(1..=Self::MAX_MEDIA).contains(&media) {
+            return Err(ValidationError::new(
+                "media",
+                format!("needs 1-{} media, got {media}", Self::MAX_MEDIA),
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct SendMediaGroupParams {
@@ -405,6 +559,42 @@ pub struct SendVenueParams {
     pub reply_markup: Option<ReplyMarkup>,
 }
 
+impl SendVenueParams {
+    #[must_use]
+    pub fn new<C, T, A>(chat_id: C, latitude: f64, longitude: f64, title: T, address: A) -> Self
+    where
+        C: Into<ChatId>,
+        T: Into<String>,
+        A: Into<String>,
+    {
+        Self::builder()
+            .chat_id(chat_id)
+            .latitude(latitude)
+            .longitude(longitude)
+            .title(title)
+            .address(address)
+            .build()
+    }
+
+    /// Set the Foursquare identifier and optionally its type, e.g. `arts_entertainment/aquarium`.
+    #[must_use]
+    pub fn foursquare<I: Into<String>>(mut self, id: I, venue_type: Option<String>) -> Self {
+        self.foursquare_id = Some(id.into());
+        self.foursquare_type = venue_type;
+        self
+    }
+
+    /// Set the Google Places identifier and optionally its type.
+    ///
+    /// See <https://developers.google.com/places/web-service/supported_types> for the types.
+    #[must_use]
+    pub fn google_place<I: Into<String>>(mut self, id: I, place_type: Option<String>) -> Self {
+        self.google_place_id = Some(id.into());
+        self.google_place_type = place_type;
+        self
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct SendContactParams {
@@ -425,6 +615,153 @@ pub struct SendContactParams {
     pub reply_markup: Option<ReplyMarkup>,
 }
 
+impl SendContactParams {
+    /// Params sending the contact described by `vcard`, using its first phone number.
+    pub fn from_vcard<C: Into<ChatId>>(chat_id: C, vcard: &VCard) -> Result<Self, ValidationError> {
+        let phone_number = vcard
+            .phones
+            .first()
+            .ok_or_else(|| ValidationError::new("phone_number", "the vCard has no phone number"))?;
+        require_non_empty("first_name", &vcard.first_name)?;
+        Ok(Self::builder()
+            .chat_id(chat_id)
+            .phone_number(phone_number.clone())
+            .first_name(vcard.first_name.clone())
+            .maybe_last_name(vcard.last_name.clone())
+            .vcard(vcard.to_string())
+            .build())
+    }
+}
+
+/// Contact card in the vCard 3.0 format for the `vcard` of [`SendContactParams`].
+///
+/// The [`Display`](std::fmt::Display) implementation produces the vCard with `\`, `,`, `;`
+/// and line breaks escaped in the values.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VCard {
+    pub first_name: String,
+    pub last_name: Option<String>,
+    pub phones: Vec<String>,
+    pub emails: Vec<String>,
+    pub organization: Option<String>,
+}
+
+impl VCard {
+    #[must_use]
+    pub fn new<F: Into<String>>(first_name: F) -> Self {
+        Self {
+            first_name: first_name.into(),
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn last_name<L: Into<String>>(mut self, last_name: L) -> Self {
+        self.last_name = Some(last_name.into());
+        self
+    }
+
+    #[must_use]
+    pub fn phone<P: Into<String>>(mut self, phone: P) -> Self {
+        self.phones.push(phone.into());
+        self
+    }
+
+    #[must_use]
+    pub fn email<E: Into<String>>(mut self, email: E) -> Self {
+        self.emails.push(email.into());
+        self
+    }
+
+    #[must_use]
+    pub fn organization<O: Into<String>>(mut self, organization: O) -> Self {
+        self.organization = Some(organization.into());
+        self
+    }
+
+    /// Unescaped phone numbers of the `TEL` lines of a vCard.
+    #[must_use]
+    pub fn phone_numbers(vcard: &str) -> Vec<String> {
+        vcard
+            .lines()
+            .filter_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                let name = name.split(';').next()?;
+                name.eq_ignore_ascii_case("TEL")
+                    .then(|| unescape_vcard(value))
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for VCard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let last_name = self.last_name.as_deref().unwrap_or_default();
+        let full_name = if last_name.is_empty() {
+            self.first_name.clone()
+        } else {
+            format!("{} {last_name}", self.first_name)
+        };
+        write!(f, "BEGIN:VCARD\r\nVERSION:3.0\r\n")?;
+        write!(
+            f,
+            "N:{};{};;;\r\n",
+            escape_vcard(last_name),
+            escape_vcard(&self.first_name)
+        )?;
+        write!(f, "FN:{}\r\n", escape_vcard(&full_name))?;
+        for phone in &self.phones {
+            write!(f, "TEL;TYPE=CELL:{}\r\n", escape_vcard(phone))?;
+        }
+        for email in &self.emails {
+            write!(f, "EMAIL;TYPE=INTERNET:{}\r\n", escape_vcard(email))?;
+        }
+        if let Some(organization) = &self.organization {
+            write!(f, "ORG:{}\r\n", escape_vcard(organization))?;
+        }
+        write!(f, "END:VCARD")
+    }
+}
+
+fn escape_vcard(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    let mut characters = value.chars().peekable();
+    while let Some(character) = characters.next() {
+        match character {
+            '\\' | ',' | ';' => {
+                escaped.push('\\');
+                escaped.push(character);
+            }
+            '\r' => {
+                if characters.peek() == Some(&'\n') {
+                    characters.next();
+                }
+                escaped.push_str("\\n");
+            }
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+fn unescape_vcard(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut characters = value.chars();
+    while let Some(character) = characters.next() {
+        if character != '\\' {
+            unescaped.push(character);
+            continue;
+        }
+        match characters.next() {
+            Some('n' | 'N') => unescaped.push('\n'),
+            Some(escaped) => unescaped.push(escaped),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct SendPollParams {
@@ -454,6 +791,116 @@ pub struct SendPollParams {
     pub reply_markup: Option<ReplyMarkup>,
 }
 
+impl SendPollParams {
+    /// Maximum number of answer options of a poll.
+    pub const MAX_OPTIONS: usize = 12;
+    /// Minimum number of answer options of a poll.
+    pub const MIN_OPTIONS: usize = 2;
+
+    /// Check the option count, the quiz answer and the text formatting before calling `sendPoll`.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        exclusive_formatting(
+            "question_entities",
+            self.question_parse_mode,
+            self.question_entities.as_ref(),
+        )?;
+        exclusive_formatting(
+            "explanation_entities",
+            self.explanation_parse_mode,
+            self.explanation_entities.as_ref(),
+        )?;
+        let options = self.options.len();
+        if !(Self::MIN_OPTIONS..=Self::MAX_OPTIONS).contains(&options) {
+            return Err(ValidationError::new(
+                "options",
+                format!(
+                    "a poll needs {}-{} options, got {options}",
+                    Self::MIN_OPTIONS,
+                    Self::MAX_OPTIONS
+                ),
+            ));
+        }
+        if self.type_field == Some(PollType::Quiz) {
+            match self.correct_option_id {
+                None => {
+                    return Err(ValidationError::new(
+                        "correct_option_id",
+                        "is required for quizzes",
+                    ))
+                }
+                Some(index) if usize::from(index) >= options => {
+                    return Err(ValidationError::new(
+                        "correct_option_id",
+                        format!("{index} is out of range for {options} options"),
+                    ))
+                }
+                Some(_) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Step by step construction of [`SendPollParams`] which is validated on [`build`](Self::build).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PollBuilder {
+    params: SendPollParams,
+}
+
+impl PollBuilder {
+    #[must_use]
+    pub fn new<C: Into<ChatId>, Q: Into<String>>(chat_id: C, question: Q) -> Self {
+        Self {
+            params: SendPollParams::builder()
+                .chat_id(chat_id)
+                .question(question)
+                .options(Vec::new())
+                .build(),
+        }
+    }
+
+    #[must_use]
+    pub fn add_option<T: Into<String>>(mut self, text: T) -> Self {
+        self.params
+            .options
+            .push(InputPollOption::builder().text(text).build());
+        self
+    }
+
+    /// Add an option with formatted text, see [`InputPollOption::markdown`].
+    #[must_use]
+    pub fn add_formatted_option(mut self, option: InputPollOption) -> Self {
+        self.params.options.push(option);
+        self
+    }
+
+    /// Turn the poll into a quiz with the option at `correct_index` (counting from 0) as the answer.
+    #[must_use]
+    pub fn quiz<E: Into<String>>(mut self, correct_index: u8, explanation: E) -> Self {
+        self.params.type_field = Some(PollType::Quiz);
+        self.params.correct_option_id = Some(correct_index);
+        self.params.explanation = Some(explanation.into());
+        self
+    }
+
+    #[must_use]
+    pub const fn anonymous(mut self, is_anonymous: bool) -> Self {
+        self.params.is_anonymous = Some(is_anonymous);
+        self
+    }
+
+    #[must_use]
+    pub const fn multiple_answers(mut self, allows_multiple_answers: bool) -> Self {
+        self.params.allows_multiple_answers = Some(allows_multiple_answers);
+        self
+    }
+
+    pub fn build(self) -> Result<SendPollParams, ValidationError> {
+        self.params.validate()?;
+        Ok(self.params)
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct SendDiceParams {
@@ -502,6 +949,20 @@ pub struct SetMessageReactionParams {
     pub is_big: Option<bool>,
 }
 
+impl SetMessageReactionParams {
+    /// Params removing all reactions of the bot from the message.
+    ///
+    /// Telegram clears the reactions when an empty `reaction` list is sent.
+    #[must_use]
+    pub fn clear<C: Into<ChatId>>(chat_id: C, message_id: i32) -> Self {
+        Self::builder()
+            .chat_id(chat_id)
+            .message_id(message_id)
+            .reaction(Vec::new())
+            .build()
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Copy, Eq)]
 pub struct GetUserProfilePhotosParams {
@@ -518,6 +979,36 @@ pub struct SetUserEmojiStatusParams {
     pub emoji_status_expiration_date: Option<u64>,
 }
 
+impl SetUserEmojiStatusParams {
+    /// Set the custom emoji `custom_emoji_id` as the user's emoji status without expiration.
+    #[must_use]
+    pub fn new<E: Into<String>>(user_id: u64, custom_emoji_id: E) -> Self {
+        Self::builder()
+            .user_id(user_id)
+            .emoji_status_custom_emoji_id(custom_emoji_id)
+            .build()
+    }
+
+    /// Remove the user's emoji status.
+    ///
+    /// Telegram removes the status when an empty custom emoji id is sent.
+    #[must_use]
+    pub fn clear(user_id: u64) -> Self {
+        Self::new(user_id, "")
+    }
+
+    /// Let the emoji status expire `duration` from now.
+    #[must_use]
+    pub fn expires_in(self, duration: Duration) -> Self {
+        self.expires_in_at(SystemTime::now(), duration)
+    }
+
+    fn expires_in_at(mut self, now: SystemTime, duration: Duration) -> Self {
+        self.emoji_status_expiration_date = Some(expire_date_after(now, duration));
+        self
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct GetFileParams {
@@ -533,6 +1024,21 @@ pub struct BanChatMemberParams {
     pub revoke_messages: Option<bool>,
 }
 
+impl BanChatMemberParams {
+    /// Ban the user for `duration` from now.
+    ///
+    /// Telegram bans forever when the ban lasts less than 30 seconds or more than 366 days.
+    #[must_use]
+    pub fn ban_for(self, duration: Duration) -> Self {
+        self.ban_for_at(SystemTime::now(), duration)
+    }
+
+    fn ban_for_at(mut self, now: SystemTime, duration: Duration) -> Self {
+        self.until_date = Some(expire_date_after(now, duration));
+        self
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct UnbanChatMemberParams {
@@ -551,6 +1057,22 @@ pub struct RestrictChatMemberParams {
     pub until_date: Option<u64>,
 }
 
+impl RestrictChatMemberParams {
+    /// Lift the restriction `duration` from now, e.g. to mute the user for an hour.
+    ///
+    /// Telegram restricts forever when the restriction lasts less than 30 seconds or more than
+    /// 366 days.
+    #[must_use]
+    pub fn restrict_for(self, duration: Duration) -> Self {
+        self.restrict_for_at(SystemTime::now(), duration)
+    }
+
+    fn restrict_for_at(mut self, now: SystemTime, duration: Duration) -> Self {
+        self.until_date = Some(expire_date_after(now, duration));
+        self
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct PromoteChatMemberParams {
@@ -574,6 +1096,42 @@ pub struct PromoteChatMemberParams {
     pub can_manage_direct_messages: Option<bool>,
 }
 
+impl PromoteChatMemberParams {
+    /// Promote the user with every administrator right granted.
+    #[must_use]
+    pub fn full_admin<C: Into<ChatId>>(chat_id: C, user_id: u64) -> Self {
+        Self::with_all_rights(chat_id.into(), user_id, true)
+    }
+
+    /// Revoke every administrator right, which demotes the user to a regular member.
+    #[must_use]
+    pub fn demote<C: Into<ChatId>>(chat_id: C, user_id: u64) -> Self {
+        Self::with_all_rights(chat_id.into(), user_id, false)
+    }
+
+    fn with_all_rights(chat_id: ChatId, user_id: u64, value: bool) -> Self {
+        Self::builder()
+            .chat_id(chat_id)
+            .user_id(user_id)
+            .can_manage_chat(value)
+            .can_post_messages(value)
+            .can_edit_messages(value)
+            .can_delete_messages(value)
+            .can_post_stories(value)
+            .can_edit_stories(value)
+            .can_delete_stories(value)
+            .can_manage_video_chats(value)
+            .can_restrict_members(value)
+            .can_promote_members(value)
+            .can_change_info(value)
+            .can_invite_users(value)
+            .can_pin_messages(value)
+            .can_manage_topics(value)
+            .can_manage_direct_messages(value)
+            .build()
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct SetChatAdministratorCustomTitleParams {
@@ -631,6 +1189,128 @@ pub struct EditChatInviteLinkParams {
     pub creates_join_request: Option<bool>,
 }
 
+/// Shared options of `createChatInviteLink` and `editChatInviteLink`, validated when the params
+/// are built.
+///
+/// `member_limit` and `creates_join_request` can't be combined: links requiring approval have no
+/// member limit.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InviteLinkBuilder {
+    name: Option<String>,
+    expire_date: Option<u64>,
+    member_limit: Option<u32>,
+    creates_join_request: bool,
+}
+
+impl InviteLinkBuilder {
+    /// Maximum number of users that can join with a limited link.
+    pub const MAX_MEMBER_LIMIT: u32 = 99_999;
+    /// Maximum length of the link name.
+    pub const MAX_NAME_LENGTH: usize = 32;
+
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn name<N: Into<String>>(mut self, name: N) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Expire the link at the given unix timestamp.
+    #[must_use]
+    pub const fn expire_date(mut self, expire_date: u64) -> Self {
+        self.expire_date = Some(expire_date);
+        self
+    }
+
+    /// Expire the link `ttl` from now.
+    #[must_use]
+    pub fn expires_in(self, ttl: Duration) -> Self {
+        self.expire_date(expire_date_after(SystemTime::now(), ttl))
+    }
+
+    #[must_use]
+    pub const fn member_limit(mut self, member_limit: u32) -> Self {
+        self.member_limit = Some(member_limit);
+        self
+    }
+
+    /// Users joining with the link have to be approved by an administrator.
+    #[must_use]
+    pub const fn creates_join_request(mut self) -> Self {
+        self.creates_join_request = true;
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(name) = &self.name {
+            if name.chars().count() > Self::MAX_NAME_LENGTH {
+                return Err(ValidationError::new(
+                    "name",
+                    format!("must be at most {} characters", Self::MAX_NAME_LENGTH),
+                ));
+            }
+        }
+        if let Some(member_limit) = self.member_limit {
+            if !(1..=Self::MAX_MEMBER_LIMIT).contains(&member_limit) {
+                return Err(ValidationError::new(
+                    "member_limit",
+                    format!("must be between 1 and {}", Self::MAX_MEMBER_LIMIT),
+                ));
+            }
+            if self.creates_join_request {
+                return Err(ValidationError::new(
+                    "member_limit",
+                    "can't be combined with creates_join_request",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn build_create<C: Into<ChatId>>(
+        self,
+        chat_id: C,
+    ) -> Result<CreateChatInviteLinkParams, ValidationError> {
+        self.validate()?;
+        Ok(CreateChatInviteLinkParams {
+            chat_id: chat_id.into(),
+            name: self.name,
+            expire_date: self.expire_date,
+            member_limit: self.member_limit,
+            creates_join_request: self.creates_join_request.then_some(true),
+        })
+    }
+
+    pub fn build_edit<C: Into<ChatId>, L: Into<String>>(
+        self,
+        chat_id: C,
+        invite_link: L,
+    ) -> Result<EditChatInviteLinkParams, ValidationError> {
+        self.validate()?;
+        Ok(EditChatInviteLinkParams {
+            chat_id: chat_id.into(),
+            invite_link: invite_link.into(),
+            name: self.name,
+            expire_date: self.expire_date,
+            member_limit: self.member_limit,
+            creates_join_request: self.creates_join_request.then_some(true),
+        })
+    }
+}
+
+/// Unix timestamp `ttl` after `now`, rounded up to whole seconds.
+fn expire_date_after(now: SystemTime, ttl: Duration) -> u64 {
+    let since_epoch = now
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .saturating_add(ttl);
+    since_epoch.as_secs() + u64::from(since_epoch.subsec_nanos() > 0)
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct CreateChatSubscriptionInviteLinkParams {
@@ -640,6 +1320,50 @@ pub struct CreateChatSubscriptionInviteLinkParams {
     pub subscription_price: u16,
 }
 
+impl CreateChatSubscriptionInviteLinkParams {
+    pub const MAX_SUBSCRIPTION_PRICE: u16 = 10_000;
+    pub const MIN_SUBSCRIPTION_PRICE: u16 = 1;
+    /// The only `subscription_period` Telegram currently accepts: 30 days in seconds.
+    pub const SUBSCRIPTION_PERIOD: u32 = 2_592_000;
+
+    /// Create a link charging `subscription_price` Telegram Stars every 30 days, rejecting prices
+    /// outside of 1-10000.
+    pub fn new<C: Into<ChatId>>(
+        chat_id: C,
+        subscription_price: u16,
+    ) -> Result<Self, ValidationError> {
+        let params = Self::builder()
+            .chat_id(chat_id)
+            .subscription_period(Self::SUBSCRIPTION_PERIOD)
+            .subscription_price(subscription_price)
+            .build();
+        params.validate()?;
+        Ok(params)
+    }
+
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.subscription_period != Self::SUBSCRIPTION_PERIOD {
+            return Err(ValidationError::new(
+                "subscription_period",
+                format!("must be {} seconds (30 days)", Self::SUBSCRIPTION_PERIOD),
+            ));
+        }
+        if !(Self::MIN_SUBSCRIPTION_PRICE..=Self::MAX_SUBSCRIPTION_PRICE)
+            .contains(&self.subscription_price)
+        {
+            return Err(ValidationError::new(
+                "subscription_price",
+                format!(
+                    "must be between {} and {} Telegram Stars",
+                    Self::MIN_SUBSCRIPTION_PRICE,
+                    Self::MAX_SUBSCRIPTION_PRICE
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct EditChatSubscriptionInviteLinkParams {
@@ -850,6 +1574,89 @@ pub struct AnswerCallbackQueryParams {
     pub cache_time: Option<u32>,
 }
 
+impl AnswerCallbackQueryParams {
+    /// Maximum length of the notification `text`.
+    pub const MAX_TEXT_LENGTH: usize = 200;
+
+    /// Show `text` as a notification at the top of the chat screen.
+    #[must_use]
+    pub fn toast<I: Into<String>, T: Into<String>>(callback_query_id: I, text: T) -> Self {
+        Self::builder()
+            .callback_query_id(callback_query_id)
+            .text(text)
+            .build()
+    }
+
+    /// Show `text` in an alert the user has to dismiss.
+    #[must_use]
+    pub fn alert<I: Into<String>, T: Into<String>>(callback_query_id: I, text: T) -> Self {
+        Self::builder()
+            .callback_query_id(callback_query_id)
+            .text(text)
+            .show_alert(true)
+            .build()
+    }
+
+    /// Open `url` on the client: the game of a `callback_game` button or a deep link like
+    /// `t.me/your_bot?start=XXXX`.
+    #[must_use]
+    pub fn url<U: Into<String>>(mut self, url: U) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Let clients cache the answer for `cache_time` seconds.
+    #[must_use]
+    pub const fn cache_time(mut self, cache_time: u32) -> Self {
+        self.cache_time = Some(cache_time);
+        self
+    }
+
+    /// Check that the answer fits `query`.
+    ///
+    /// A `url` is only accepted for queries from a game button or as a deep link to a bot.
+    pub fn validate_for(&self, query: &CallbackQuery) -> Result<(), ValidationError> {
+        if self.callback_query_id != query.id {
+            return Err(ValidationError::new(
+                "callback_query_id",
+                "does not match the callback query",
+            ));
+        }
+        if let Some(text) = &self.text {
+            if text.chars().count() > Self::MAX_TEXT_LENGTH {
+                return Err(ValidationError::new(
+                    "text",
+                    format!("must be at most {} characters", Self::MAX_TEXT_LENGTH),
+                ));
+            }
+        }
+        if let Some(url) = &self.url {
+            if query.game_short_name.is_none() && !is_bot_deep_link(url) {
+                return Err(ValidationError::new(
+                    "url",
+                    "must be a t.me/<bot>?start= deep link unless the query is from a game",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether `url` is a `t.me/<bot>?start=` link.
+fn is_bot_deep_link(url: &str) -> bool {
+    let path = ["https://t.me/", "http://t.me/", "t.me/"]
+        .iter()
+        .find_map(|prefix| url.strip_prefix(prefix));
+    let Some((bot, query)) = path.and_then(|path| path.split_once('?')) else {
+        return false;
+    };
+    !bot.is_empty()
+        && !bot.contains('/')
+        && query
+            .split('&')
+            .any(|parameter| parameter.starts_with("start="))
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct GetUserChatBoostsParams {
@@ -871,6 +1678,97 @@ pub struct SetMyCommandsParams {
     pub language_code: Option<String>,
 }
 
+impl SetMyCommandsParams {
+    pub const MAX_COMMANDS: usize = 100;
+    pub const MAX_COMMAND_LENGTH: usize = 32;
+    pub const MAX_DESCRIPTION_LENGTH: usize = 256;
+    pub const MIN_DESCRIPTION_LENGTH: usize = 3;
+
+    /// Check the number of commands and every command name and description.
+    ///
+    /// Command names have to be 1-32 characters of lowercase letters, digits and underscores,
+    /// descriptions 3-256 characters.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        validate_commands(&self.commands)
+    }
+}
+
+fn validate_commands(commands: &[BotCommand]) -> Result<(), ValidationError> {
+    if commands.len() > SetMyCommandsParams::MAX_COMMANDS {
+        return Err(ValidationError::new(
+            "commands",
+            format!(
+                "at most {} commands are allowed, got {}",
+                SetMyCommandsParams::MAX_COMMANDS,
+                commands.len()
+            ),
+        ));
+    }
+    for BotCommand {
+        command,
+        description,
+    } in commands
+    {
+        let valid_name = (1..=SetMyCommandsParams::MAX_COMMAND_LENGTH).contains(&command.len())
+            && command
+                .bytes()
+                .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit() || byte == b'_');
+        if !valid_name {
+            return Err(ValidationError::new(
+                "commands",
+                format!(
+                    "command {command:?} has to be 1-{} lowercase letters, digits or underscores",
+                    SetMyCommandsParams::MAX_COMMAND_LENGTH
+                ),
+            ));
+        }
+        let description_length = description.chars().count();
+        if !(SetMyCommandsParams::MIN_DESCRIPTION_LENGTH
+            ..=SetMyCommandsParams::MAX_DESCRIPTION_LENGTH)
+            .contains(&description_length)
+        {
+            return Err(ValidationError::new(
+                "commands",
+                format!(
+                    "description of {command:?} has to be {}-{} characters, got {description_length}",
+                    SetMyCommandsParams::MIN_DESCRIPTION_LENGTH,
+                    SetMyCommandsParams::MAX_DESCRIPTION_LENGTH
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Step by step construction of the commands for `setMyCommands`, validated on [`build`](Self::build).
+///
+/// See [`SetMyCommandsParams::validate`] for the rules.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommandsBuilder {
+    commands: Vec<BotCommand>,
+}
+
+impl CommandsBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn command<N: Into<String>, D: Into<String>>(mut self, name: N, description: D) -> Self {
+        self.commands.push(BotCommand {
+            command: name.into(),
+            description: description.into(),
+        });
+        self
+    }
+
+    pub fn build(self) -> Result<Vec<BotCommand>, ValidationError> {
+        validate_commands(&self.commands)?;
+        Ok(self.commands)
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct SetMyNameParams {
@@ -973,6 +1871,23 @@ pub struct EditMessageReplyMarkupParams {
     pub reply_markup: Option<InlineKeyboardMarkup>,
 }
 
+impl EditMessageReplyMarkupParams {
+    /// Params removing the inline keyboard of a message.
+    ///
+    /// Sends an explicitly empty keyboard. A `reply_markup` of `None` is not serialized at all,
+    /// which leaves it to Telegram how to treat the missing keyboard.
+    #[must_use]
+    pub fn clear<C: Into<ChatId>>(chat_id: C, message_id: i32) -> Self {
+        Self::builder()
+            .chat_id(chat_id)
+            .message_id(message_id)
+            .reply_markup(InlineKeyboardMarkup {
+                inline_keyboard: Vec::new(),
+            })
+            .build()
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct StopPollParams {
@@ -1012,6 +1927,11 @@ pub struct DeleteMessagesParams {
     pub message_ids: Vec<i32>,
 }
 
+impl DeleteMessagesParams {
+    /// Maximum number of `message_ids` Telegram accepts in one call.
+    pub const MAX_MESSAGE_IDS: usize = 100;
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct SendStickerParams {
@@ -1054,6 +1974,73 @@ pub struct CreateNewStickerSetParams {
     pub needs_repainting: Option<bool>,
 }
 
+impl CreateNewStickerSetParams {
+    /// Maximum length of the sticker set name, including the `_by_<bot_username>` suffix.
+    pub const MAX_NAME_LENGTH: usize = 64;
+    /// Maximum number of stickers when creating a sticker set.
+    pub const MAX_STICKERS: usize = 50;
+    /// Maximum length of the sticker set title.
+    pub const MAX_TITLE_LENGTH: usize = 64;
+
+    /// Check name, title and sticker count before uploading the stickers.
+    ///
+    /// The name has to end with `_by_<bot_username>`, start with a letter and only consist of
+    /// English letters, digits and single underscores.
+    pub fn validate(&self, bot_username: &str) -> Result<(), ValidationError> {
+        let bot_username = bot_username.trim_start_matches('@');
+        let suffix = format!("_by_{bot_username}").to_lowercase();
+        if !self.name.to_lowercase().ends_with(&suffix) {
+            return Err(ValidationError::new(
+                "name",
+                format!("has to end with {suffix}"),
+            ));
+        }
+        if !self.name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return Err(ValidationError::new("name", "has to begin with a letter"));
+        }
+        if !self
+            .name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Err(ValidationError::new(
+                "name",
+                "can only contain English letters, digits and underscores",
+            ));
+        }
+        if self.name.contains("__") {
+            return Err(ValidationError::new(
+                "name",
+                "can not contain consecutive underscores",
+            ));
+        }
+        // Only ASCII is left, so the length in bytes is the number of characters.
+        if self.name.len() > Self::MAX_NAME_LENGTH {
+            return Err(ValidationError::new(
+                "name",
+                format!("has to be at most {} characters", Self::MAX_NAME_LENGTH),
+            ));
+        }
+
+        let title_length = self.title.chars().count();
+        if !(1..=Self::MAX_TITLE_LENGTH).contains(&title_length) {
+            return Err(ValidationError::new(
+                "title",
+                format!("has to be 1-{} characters", Self::MAX_TITLE_LENGTH),
+            ));
+        }
+
+        let stickers = self.stickers.len();
+        if !(1..=Self::MAX_STICKERS).contains(&stickers) {
+            return Err(ValidationError::new(
+                "stickers",
+                format!("needs 1-{} stickers, got {stickers}", Self::MAX_STICKERS),
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct GetCustomEmojiStickersParams {
@@ -1095,6 +2082,23 @@ pub struct SetStickerEmojiListParams {
     pub emoji_list: Vec<String>,
 }
 
+impl SetStickerEmojiListParams {
+    /// Maximum number of emoji associated with a sticker.
+    pub const MAX_EMOJI: usize = 20;
+
+    /// Check that 1-20 emoji are given.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let count = self.emoji_list.len();
+        if !(1..=Self::MAX_EMOJI).contains(&count) {
+            return Err(ValidationError::new(
+                "emoji_list",
+                format!("needs 1-{} emoji, got {count}", Self::MAX_EMOJI),
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct SetStickerKeywordsParams {
@@ -1102,6 +2106,39 @@ pub struct SetStickerKeywordsParams {
     pub keywords: Option<Vec<String>>,
 }
 
+impl SetStickerKeywordsParams {
+    /// Maximum number of search keywords of a sticker.
+    pub const MAX_KEYWORDS: usize = 20;
+    /// Maximum total length of all keywords in characters.
+    pub const MAX_TOTAL_LENGTH: usize = 64;
+
+    /// Check that at most 20 keywords with a total length of up to 64 characters are given.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let keywords = self.keywords.as_deref().unwrap_or_default();
+        if keywords.len() > Self::MAX_KEYWORDS {
+            return Err(ValidationError::new(
+                "keywords",
+                format!(
+                    "at most {} keywords are allowed, got {}",
+                    Self::MAX_KEYWORDS,
+                    keywords.len()
+                ),
+            ));
+        }
+        let total_length: usize = keywords.iter().map(|keyword| keyword.chars().count()).sum();
+        if total_length > Self::MAX_TOTAL_LENGTH {
+            return Err(ValidationError::new(
+                "keywords",
+                format!(
+                    "total length has to be at most {} characters, got {total_length}",
+                    Self::MAX_TOTAL_LENGTH
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[apply(apistruct!)]
 pub struct SetStickerMaskPositionParams {
     pub sticker: String,
@@ -1149,6 +2186,42 @@ pub struct SendGiftParams {
     pub text_entities: Option<Vec<MessageEntity>>,
 }
 
+impl SendGiftParams {
+    /// Send the gift `gift_id` to a user together with a formatted note.
+    pub fn with_message<G: Into<String>, T: Into<String>>(
+        user_id: u64,
+        gift_id: G,
+        text: T,
+        parse_mode: ParseMode,
+    ) -> Result<Self, ValidationError> {
+        let params = Self::builder()
+            .user_id(user_id)
+            .gift_id(gift_id)
+            .text(text)
+            .text_parse_mode(parse_mode)
+            .build();
+        params.validate()?;
+        Ok(params)
+    }
+
+    /// Check that a gift and exactly one receiver are given and the text formatting is unambiguous.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        require_non_empty("gift_id", &self.gift_id)?;
+        exclusive_formatting(
+            "text_entities",
+            self.text_parse_mode,
+            self.text_entities.as_ref(),
+        )?;
+        if self.user_id.is_some() == self.chat_id.is_some() {
+            return Err(ValidationError::new(
+                "user_id",
+                "exactly one of user_id and chat_id has to be set",
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct GiftPremiumSubscriptionParams {
@@ -1160,6 +2233,42 @@ pub struct GiftPremiumSubscriptionParams {
     pub text_entities: Option<Vec<MessageEntity>>,
 }
 
+impl GiftPremiumSubscriptionParams {
+    /// Gift `months` of Telegram Premium to the user, paying the matching
+    /// [`star_count`](PremiumMonths::star_count).
+    #[must_use]
+    pub fn new(user_id: u64, months: PremiumMonths) -> Self {
+        Self::builder()
+            .user_id(user_id)
+            .month_count(months.month_count())
+            .star_count(months.star_count())
+            .build()
+    }
+
+    /// Check that `month_count` is 3, 6 or 12, `star_count` is the price of that duration and
+    /// `text_parse_mode` and `text_entities` are not both set.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        exclusive_formatting(
+            "text_entities",
+            self.text_parse_mode.as_deref(),
+            self.text_entities.as_ref(),
+        )?;
+        let months = PremiumMonths::try_from(self.month_count)?;
+        if self.star_count != months.star_count() {
+            return Err(ValidationError::new(
+                "star_count",
+                format!(
+                    "{} months cost {} stars, got {}",
+                    self.month_count,
+                    months.star_count(),
+                    self.star_count
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct VerifyUserParams {
@@ -1174,6 +2283,74 @@ pub struct VerifyChatParams {
     pub custom_description: Option<String>,
 }
 
+impl VerifyUserParams {
+    pub const MAX_DESCRIPTION_LENGTH: usize = MAX_VERIFICATION_DESCRIPTION_LENGTH;
+
+    /// Verify the user with the bot's default verification description.
+    #[must_use]
+    pub fn new(user_id: u64) -> Self {
+        Self::builder().user_id(user_id).build()
+    }
+
+    /// Verify the user showing `custom_description`, which may be empty.
+    pub fn with_description<D: Into<String>>(
+        user_id: u64,
+        custom_description: D,
+    ) -> Result<Self, ValidationError> {
+        let params = Self::builder()
+            .user_id(user_id)
+            .custom_description(custom_description)
+            .build();
+        params.validate()?;
+        Ok(params)
+    }
+
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        check_verification_description(self.custom_description.as_deref())
+    }
+}
+
+impl VerifyChatParams {
+    pub const MAX_DESCRIPTION_LENGTH: usize = MAX_VERIFICATION_DESCRIPTION_LENGTH;
+
+    /// Verify the chat with the bot's default verification description.
+    #[must_use]
+    pub fn new<C: Into<ChatId>>(chat_id: C) -> Self {
+        Self::builder().chat_id(chat_id).build()
+    }
+
+    /// Verify the chat showing `custom_description`, which may be empty.
+    pub fn with_description<C: Into<ChatId>, D: Into<String>>(
+        chat_id: C,
+        custom_description: D,
+    ) -> Result<Self, ValidationError> {
+        let params = Self::builder()
+            .chat_id(chat_id)
+            .custom_description(custom_description)
+            .build();
+        params.validate()?;
+        Ok(params)
+    }
+
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        check_verification_description(self.custom_description.as_deref())
+    }
+}
+
+const MAX_VERIFICATION_DESCRIPTION_LENGTH: usize = 70;
+
+fn check_verification_description(description: Option<&str>) -> Result<(), ValidationError> {
+    if let Some(description) = description {
+        if description.chars().count() > MAX_VERIFICATION_DESCRIPTION_LENGTH {
+            return Err(ValidationError::new(
+                "custom_description",
+                format!("must be at most {MAX_VERIFICATION_DESCRIPTION_LENGTH} characters"),
+            ));
+        }
+    }
+    Ok(())
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct RemoveUserVerificationParams {
@@ -1201,6 +2378,11 @@ pub struct DeleteBusinessMessagesParams {
     pub message_ids: Vec<i32>,
 }
 
+impl DeleteBusinessMessagesParams {
+    /// Maximum number of `message_ids` Telegram accepts in one call.
+    pub const MAX_MESSAGE_IDS: usize = 100;
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct SetBusinessAccountNameParams {
@@ -1216,6 +2398,85 @@ pub struct SetBusinessAccountUsernameParams {
     pub username: Option<String>,
 }
 
+impl SetBusinessAccountNameParams {
+    /// Maximum length of the first and the last name.
+    pub const MAX_NAME_LENGTH: usize = 64;
+
+    /// Check that the first name has 1-64 characters and the last name at most 64.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let first_name_length = self.first_name.chars().count();
+        if !(1..=Self::MAX_NAME_LENGTH).contains(&first_name_length) {
+            return Err(ValidationError::new(
+                "first_name",
+                format!("has to be 1-{} characters", Self::MAX_NAME_LENGTH),
+            ));
+        }
+        if let Some(last_name) = &self.last_name {
+            if last_name.chars().count() > Self::MAX_NAME_LENGTH {
+                return Err(ValidationError::new(
+                    "last_name",
+                    format!("has to be at most {} characters", Self::MAX_NAME_LENGTH),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SetBusinessAccountUsernameParams {
+    /// Maximum length of a username.
+    pub const MAX_USERNAME_LENGTH: usize = 32;
+    /// Minimum length of a username.
+    pub const MIN_USERNAME_LENGTH: usize = 5;
+
+    /// Check the username against Telegram's rules, a missing or empty username removes it.
+    ///
+    /// Usernames have 5-32 characters, begin with a letter, only consist of English letters,
+    /// digits and underscores and don't end with an underscore.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let Some(username) = self
+            .username
+            .as_deref()
+            .filter(|username| !username.is_empty())
+        else {
+            return Ok(());
+        };
+        if !username.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return Err(ValidationError::new(
+                "username",
+                "has to begin with a letter",
+            ));
+        }
+        if !username
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Err(ValidationError::new(
+                "username",
+                "can only contain English letters, digits and underscores",
+            ));
+        }
+        // Only ASCII is left, so the length in bytes is the number of characters.
+        if !(Self::MIN_USERNAME_LENGTH..=Self::MAX_USERNAME_LENGTH).contains(&username.len()) {
+            return Err(ValidationError::new(
+                "username",
+                format!(
+                    "has to be {}-{} characters",
+                    Self::MIN_USERNAME_LENGTH,
+                    Self::MAX_USERNAME_LENGTH
+                ),
+            ));
+        }
+        if username.ends_with('_') {
+            return Err(ValidationError::new(
+                "username",
+                "can not end with an underscore",
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct SetBusinessAccountBioParams {
@@ -1245,6 +2506,35 @@ pub struct SetBusinessAccountGiftSettingsParams {
     pub accepted_gift_types: AcceptedGiftTypes,
 }
 
+impl SetBusinessAccountGiftSettingsParams {
+    /// Accept every type of gift and show the gift button.
+    #[must_use]
+    pub fn accept_all<B: Into<String>>(business_connection_id: B) -> Self {
+        Self::accepting(business_connection_id, AcceptedGiftTypes::ALL)
+    }
+
+    /// Accept no gifts and hide the gift button.
+    #[must_use]
+    pub fn accept_none<B: Into<String>>(business_connection_id: B) -> Self {
+        Self::accepting(business_connection_id, AcceptedGiftTypes::NONE)
+    }
+
+    /// Accept the given types of gifts, showing the gift button when any type is accepted.
+    ///
+    /// Single types can be toggled with [`AcceptedGiftTypes::builder`].
+    #[must_use]
+    pub fn accepting<B: Into<String>>(
+        business_connection_id: B,
+        accepted_gift_types: AcceptedGiftTypes,
+    ) -> Self {
+        Self::builder()
+            .business_connection_id(business_connection_id)
+            .show_gift_button(accepted_gift_types.accepts_any())
+            .accepted_gift_types(accepted_gift_types)
+            .build()
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct GetBusinessAccountStarBalanceParams {
@@ -1255,7 +2545,7 @@ pub struct GetBusinessAccountStarBalanceParams {
 #[derive(Eq)]
 pub struct TransferBusinessAccountStarsParams {
     pub business_connection_id: String,
-    pub start_count: u32,
+    pub star_count: u32,
 }
 
 #[apply(apistruct!)]
@@ -1272,6 +2562,34 @@ pub struct GetBusinessAccountGiftsParams {
     pub limit: Option<u32>,
 }
 
+impl GetBusinessAccountGiftsParams {
+    /// Only list unique gifts, excluding limited and unlimited regular gifts.
+    #[must_use]
+    pub const fn unique_only(mut self) -> Self {
+        self.exclude_unlimited = Some(true);
+        self.exclude_limited = Some(true);
+        self.exclude_unique = None;
+        self
+    }
+
+    /// Only list regular gifts, excluding unique gifts.
+    #[must_use]
+    pub const fn regular_only(mut self) -> Self {
+        self.exclude_unlimited = None;
+        self.exclude_limited = None;
+        self.exclude_unique = Some(true);
+        self
+    }
+
+    /// Only list gifts which are displayed on the account's profile page.
+    #[must_use]
+    pub const fn saved_only(mut self) -> Self {
+        self.exclude_unsaved = Some(true);
+        self.exclude_saved = None;
+        self
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct ConvertGiftToStarsParams {
@@ -1279,6 +2597,26 @@ pub struct ConvertGiftToStarsParams {
     pub owner_gift_id: String,
 }
 
+impl ConvertGiftToStarsParams {
+    /// Create the parameters, rejecting empty ids.
+    pub fn new<B: Into<String>, G: Into<String>>(
+        business_connection_id: B,
+        owned_gift_id: G,
+    ) -> Result<Self, ValidationError> {
+        let params = Self::builder()
+            .business_connection_id(business_connection_id)
+            .owner_gift_id(owned_gift_id)
+            .build();
+        params.validate()?;
+        Ok(params)
+    }
+
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        require_non_empty("business_connection_id", &self.business_connection_id)?;
+        require_non_empty("owner_gift_id", &self.owner_gift_id)
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct UpgradeGiftParams {
@@ -1288,6 +2626,36 @@ pub struct UpgradeGiftParams {
     pub star_count: Option<u32>,
 }
 
+impl UpgradeGiftParams {
+    /// Upgrade the gift, dropping its original text, sender and receiver.
+    #[must_use]
+    pub fn new<B: Into<String>, G: Into<String>>(
+        business_connection_id: B,
+        owned_gift_id: G,
+    ) -> Self {
+        Self::builder()
+            .business_connection_id(business_connection_id)
+            .owner_gift_id(owned_gift_id)
+            .build()
+    }
+
+    /// Keep the original gift text, sender and receiver in the upgraded gift.
+    #[must_use]
+    pub const fn keep_details(mut self) -> Self {
+        self.keep_original_details = Some(true);
+        self
+    }
+
+    /// Pay the upgrade with `star_count` Stars from the business account balance.
+    ///
+    /// Only needed when the upgrade was not prepaid by the sender.
+    #[must_use]
+    pub const fn pay_stars(mut self, star_count: u32) -> Self {
+        self.star_count = Some(star_count);
+        self
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct TransferGiftParams {
@@ -1297,6 +2665,53 @@ pub struct TransferGiftParams {
     pub star_count: Option<u32>,
 }
 
+impl TransferGiftParams {
+    /// Create the parameters, rejecting empty ids.
+    pub fn new<B: Into<String>, G: Into<String>>(
+        business_connection_id: B,
+        owned_gift_id: G,
+        new_owner_chat_id: i64,
+        star_count: Option<u32>,
+    ) -> Result<Self, ValidationError> {
+        let params = Self::builder()
+            .business_connection_id(business_connection_id)
+            .owner_gift_id(owned_gift_id)
+            .new_owner_chat_id(new_owner_chat_id)
+            .maybe_star_count(star_count)
+            .build();
+        params.validate()?;
+        Ok(params)
+    }
+
+    /// Transfer an owned unique gift, paying the transfer fee it requires.
+    ///
+    /// Fails when the gift has no `owned_gift_id` or can not be transferred.
+    pub fn for_gift<B: Into<String>>(
+        business_connection_id: B,
+        gift: &OwnedGiftUnique,
+        new_owner_chat_id: i64,
+    ) -> Result<Self, ValidationError> {
+        if gift.can_be_transferred == Some(false) {
+            return Err(ValidationError::new(
+                "owner_gift_id",
+                "the gift can not be transferred",
+            ));
+        }
+        let owned_gift_id = gift.owned_gift_id.clone().unwrap_or_default();
+        Self::new(
+            business_connection_id,
+            owned_gift_id,
+            new_owner_chat_id,
+            gift.transfer_star_count.filter(|stars| *stars > 0),
+        )
+    }
+
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        require_non_empty("business_connection_id", &self.business_connection_id)?;
+        require_non_empty("owner_gift_id", &self.owner_gift_id)
+    }
+}
+
 #[apply(apistruct!)]
 pub struct PostStoryParams {
     pub business_connection_id: String,
@@ -1310,6 +2725,15 @@ pub struct PostStoryParams {
     pub protect_content: Option<bool>,
 }
 
+impl PostStoryParams {
+    /// Append a clickable area to the story.
+    #[must_use]
+    pub fn add_area(mut self, area: StoryArea) -> Self {
+        self.areas.get_or_insert_with(Vec::new).push(area);
+        self
+    }
+}
+
 #[apply(apistruct!)]
 pub struct EditStoryParams {
     pub business_connection_id: String,
@@ -1321,6 +2745,15 @@ pub struct EditStoryParams {
     pub areas: Option<Vec<StoryArea>>,
 }
 
+impl EditStoryParams {
+    /// Append a clickable area to the story.
+    #[must_use]
+    pub fn add_area(mut self, area: StoryArea) -> Self {
+        self.areas.get_or_insert_with(Vec::new).push(area);
+        self
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct DeleteStoryParams {
@@ -1338,6 +2771,24 @@ pub struct AnswerInlineQueryParams {
     pub button: Option<InlineQueryResultsButton>,
 }
 
+impl AnswerInlineQueryParams {
+    /// Answer showing `button` above the `results`.
+    ///
+    /// See [`InlineQueryResultsButton::web_app`] and [`InlineQueryResultsButton::start_parameter`].
+    #[must_use]
+    pub fn with_button<I: Into<String>>(
+        inline_query_id: I,
+        results: Vec<InlineQueryResult>,
+        button: InlineQueryResultsButton,
+    ) -> Self {
+        Self::builder()
+            .inline_query_id(inline_query_id)
+            .results(results)
+            .button(button)
+            .build()
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct SendInvoiceParams {
@@ -1348,7 +2799,8 @@ pub struct SendInvoiceParams {
     pub description: String,
     pub payload: String,
     pub provider_token: Option<String>,
-    pub currency: String,
+    #[builder(into)]
+    pub currency: Currency,
     pub prices: Vec<LabeledPrice>,
     pub max_tip_amount: Option<u32>,
     pub suggested_tip_amounts: Option<Vec<u32>>,
@@ -1382,7 +2834,8 @@ pub struct CreateInvoiceLinkParams {
     pub description: String,
     pub payload: String,
     pub provider_token: Option<String>,
-    pub currency: String,
+    #[builder(into)]
+    pub currency: Currency,
     pub prices: Vec<LabeledPrice>,
     pub subscription_period: Option<u32>,
     pub max_tip_amount: Option<u32>,
@@ -1401,6 +2854,50 @@ pub struct CreateInvoiceLinkParams {
     pub is_flexible: Option<bool>,
 }
 
+impl CreateInvoiceLinkParams {
+    /// Check the prices and the [Telegram Stars](Currency::Xtr) restrictions.
+    ///
+    /// Invoices in Stars must not have a `provider_token` or tips and need exactly one price.
+    /// Subscriptions are only supported in Stars.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.prices.is_empty() {
+            return Err(ValidationError::new("prices", "needs at least one price"));
+        }
+        if self.currency != Currency::Xtr {
+            if self.subscription_period.is_some() {
+                return Err(ValidationError::new(
+                    "subscription_period",
+                    "subscriptions have to be paid in XTR",
+                ));
+            }
+            return Ok(());
+        }
+        if self
+            .provider_token
+            .as_deref()
+            .is_some_and(|token| !token.is_empty())
+        {
+            return Err(ValidationError::new(
+                "provider_token",
+                "has to be empty for payments in XTR",
+            ));
+        }
+        if self.prices.len() > 1 {
+            return Err(ValidationError::new(
+                "prices",
+                "payments in XTR need exactly one price",
+            ));
+        }
+        if self.max_tip_amount.is_some() || self.suggested_tip_amounts.is_some() {
+            return Err(ValidationError::new(
+                "max_tip_amount",
+                "tips are not supported for payments in XTR",
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct AnswerShippingQueryParams {
@@ -1433,6 +2930,28 @@ pub struct RefundStarPaymentParams {
     pub telegram_payment_charge_id: String,
 }
 
+impl RefundStarPaymentParams {
+    /// Create the parameters, rejecting an empty `telegram_payment_charge_id`.
+    pub fn new<C: Into<String>>(
+        user_id: u64,
+        telegram_payment_charge_id: C,
+    ) -> Result<Self, ValidationError> {
+        let params = Self::builder()
+            .user_id(user_id)
+            .telegram_payment_charge_id(telegram_payment_charge_id)
+            .build();
+        params.validate()?;
+        Ok(params)
+    }
+
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        require_non_empty(
+            "telegram_payment_charge_id",
+            &self.telegram_payment_charge_id,
+        )
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct EditUserStarSubscriptionParams {
@@ -1441,6 +2960,49 @@ pub struct EditUserStarSubscriptionParams {
     pub is_canceled: bool,
 }
 
+impl EditUserStarSubscriptionParams {
+    /// Create the parameters, rejecting an empty `telegram_payment_charge_id`.
+    ///
+    /// `is_canceled` has to be passed explicitly, see [`cancel`](Self::cancel) and
+    /// [`reenable`](Self::reenable) for the two cases.
+    pub fn new<C: Into<String>>(
+        user_id: u64,
+        telegram_payment_charge_id: C,
+        is_canceled: bool,
+    ) -> Result<Self, ValidationError> {
+        let params = Self::builder()
+            .user_id(user_id)
+            .telegram_payment_charge_id(telegram_payment_charge_id)
+            .is_canceled(is_canceled)
+            .build();
+        params.validate()?;
+        Ok(params)
+    }
+
+    /// Cancel the extension of the subscription.
+    pub fn cancel<C: Into<String>>(
+        user_id: u64,
+        telegram_payment_charge_id: C,
+    ) -> Result<Self, ValidationError> {
+        Self::new(user_id, telegram_payment_charge_id, true)
+    }
+
+    /// Re-enable the extension of a previously canceled subscription.
+    pub fn reenable<C: Into<String>>(
+        user_id: u64,
+        telegram_payment_charge_id: C,
+    ) -> Result<Self, ValidationError> {
+        Self::new(user_id, telegram_payment_charge_id, false)
+    }
+
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        require_non_empty(
+            "telegram_payment_charge_id",
+            &self.telegram_payment_charge_id,
+        )
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct SetPassportDataErrorsParams {
@@ -1475,6 +3037,71 @@ pub struct SetGameScoreParams {
     pub inline_message_id: Option<String>,
 }
 
+impl SetGameScoreParams {
+    /// Params for the score of a game sent by the bot in `chat_id`.
+    #[must_use]
+    pub fn for_message(user_id: u64, score: i32, chat_id: i64, message_id: i32) -> Self {
+        Self::builder()
+            .user_id(user_id)
+            .score(score)
+            .chat_id(chat_id)
+            .message_id(message_id)
+            .build()
+    }
+
+    /// Params for the score of a game sent via inline mode.
+    #[must_use]
+    pub fn for_inline_message<I: Into<String>>(
+        user_id: u64,
+        score: i32,
+        inline_message_id: I,
+    ) -> Self {
+        Self::builder()
+            .user_id(user_id)
+            .score(score)
+            .inline_message_id(inline_message_id)
+            .build()
+    }
+
+    /// Set the score even if it is lower than the current one, e.g. to correct mistakes.
+    ///
+    /// Without it setting a lower score fails.
+    #[must_use]
+    pub const fn force(mut self) -> Self {
+        self.force = Some(true);
+        self
+    }
+
+    /// Don't edit the game message to show the new scoreboard.
+    #[must_use]
+    pub const fn no_edit(mut self) -> Self {
+        self.disable_edit_message = Some(true);
+        self
+    }
+
+    /// Check that the game message is addressed either by `chat_id` and `message_id` or by
+    /// `inline_message_id`.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let by_chat = match (self.chat_id, self.message_id) {
+            (Some(_), Some(_)) => true,
+            (None, None) => false,
+            _ => {
+                return Err(ValidationError::new(
+                    "message_id",
+                    "chat_id and message_id have to be set together",
+                ))
+            }
+        };
+        if by_chat == self.inline_message_id.is_some() {
+            return Err(ValidationError::new(
+                "inline_message_id",
+                "exactly one of chat_id with message_id and inline_message_id has to be set",
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct GetGameHighScoresParams {
@@ -1503,6 +3130,57 @@ pub struct AnswerWebAppQueryParams {
     pub result: InlineQueryResult,
 }
 
+impl AnswerWebAppQueryParams {
+    /// Params answering the Web App query with `result`, validated with
+    /// [`validate`](Self::validate).
+    ///
+    /// A result with an empty `id` gets a random UUID-like id.
+    pub fn new<I: Into<String>, R: Into<InlineQueryResult>>(
+        web_app_query_id: I,
+        result: R,
+    ) -> Result<Self, ValidationError> {
+        let mut result = result.into();
+        if result.id().is_empty() {
+            *result.id_mut() = random_result_id();
+        }
+        let params = Self::builder()
+            .web_app_query_id(web_app_query_id)
+            .result(result)
+            .build();
+        params.validate()?;
+        Ok(params)
+    }
+
+    /// Check the parameters before calling `answerWebAppQuery`.
+    ///
+    /// Games can only be sent in inline mode and are rejected for Web App queries.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        require_non_empty("web_app_query_id", &self.web_app_query_id)?;
+        if matches!(self.result, InlineQueryResult::Game(_)) {
+            return Err(ValidationError::new(
+                "result",
+                "game results can not be used to answer a Web App query",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Random id in the UUID v4 format.
+fn random_result_id() -> String {
+    let random = crate::random::random_u128();
+    // Version 4 and the RFC 4122 variant
+    let uuid = (random & !((0xf000 << 64) | (0xc << 60))) | (0x4000 << 64) | (0x8 << 60);
+    format!(
+        "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+        uuid >> 96,
+        (uuid >> 80) & 0xffff,
+        (uuid >> 64) & 0xffff,
+        (uuid >> 48) & 0xffff,
+        uuid & 0xffff_ffff_ffff,
+    )
+}
+
 #[apply(apistruct!)]
 pub struct SavePreparedInlineMessageParams {
     pub user_id: u64,
@@ -1531,3 +3209,1155 @@ pub struct GetChatMenuButtonParams {
 pub struct UnpinAllGeneralForumTopicMessagesParams {
     pub chat_id: ChatId,
 }
+
+/// Readable setters for the `disable_notification` and `protect_content` flags of the send methods.
+pub trait SendFlags: Sized {
+    /// Send silently, users will receive a notification with no sound.
+    #[must_use]
+    fn silent(self) -> Self;
+
+    /// Protect the contents of the sent message from forwarding and saving.
+    #[must_use]
+    fn protected(self) -> Self;
+}
+
+macro_rules! impl_send_flags {
+    ($($params:ident),+ $(,)?) => {
+        $(
+            impl SendFlags for $params {
+                fn silent(mut self) -> Self {
+                    self.disable_notification = Some(true);
+                    self
+                }
+
+                fn protected(mut self) -> Self {
+                    self.protect_content = Some(true);
+                    self
+                }
+            }
+        )+
+    };
+}
+
+impl_send_flags!(
+    SendMessageParams,
+    ForwardMessageParams,
+    ForwardMessagesParams,
+    CopyMessageParams,
+    CopyMessagesParams,
+    SendPhotoParams,
+    SendAudioParams,
+    SendDocumentParams,
+    SendVideoParams,
+    SendAnimationParams,
+    SendVoiceParams,
+    SendVideoNoteParams,
+    SendPaidMediaParams,
+    SendMediaGroupParams,
+    SendLocationParams,
+    SendVenueParams,
+    SendContactParams,
+    SendPollParams,
+    SendDiceParams,
+    SendChecklistParams,
+    SendStickerParams,
+    SendInvoiceParams,
+    SendGameParams,
+);
+
+/// `parse_mode` and explicit entities of the same text can't be combined.
+fn exclusive_formatting<P>(
+    entities_field: &'static str,
+    parse_mode: Option<P>,
+    entities: Option<&Vec<MessageEntity>>,
+) -> Result<(), ValidationError> {
+    if parse_mode.is_some() && entities.is_some() {
+        return Err(ValidationError::new(
+            entities_field,
+            "can't be combined with a parse mode",
+        ));
+    }
+    Ok(())
+}
+
+macro_rules! impl_formatting_validation {
+    ($($params:ident: $parse_mode:ident / $entities:ident),+ $(,)?) => {
+        $(
+            impl $params {
+                #[doc = concat!(
+                    "Check that `", stringify!($parse_mode), "` and `", stringify!($entities),
+                    "` are not both set, which Telegram rejects.\n\nNothing is checked unless this is called."
+                )]
+                pub fn validate(&self) -> Result<(), ValidationError> {
+                    exclusive_formatting(
+                        stringify!($entities),
+                        self.$parse_mode.as_ref(),
+                        self.$entities.as_ref(),
+                    )
+                }
+            }
+        )+
+    };
+}
+
+impl_formatting_validation!(
+    SendMessageParams: parse_mode / entities,
+    CopyMessageParams: parse_mode / caption_entities,
+    SendPhotoParams: parse_mode / caption_entities,
+    SendAudioParams: parse_mode / caption_entities,
+    SendDocumentParams: parse_mode / caption_entities,
+    SendVideoParams: parse_mode / caption_entities,
+    SendAnimationParams: parse_mode / caption_entities,
+    SendVoiceParams: parse_mode / caption_entities,
+    EditMessageTextParams: parse_mode / entities,
+    EditMessageCaptionParams: parse_mode / caption_entities,
+    PostStoryParams: parse_mode / caption_entities,
+    EditStoryParams: parse_mode / caption_entities,
+);
+
+fn require_non_empty(field: &'static str, value: &str) -> Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        return Err(ValidationError::new(field, "must not be empty"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn promote_rights(params: &PromoteChatMemberParams) -> [Option<bool>; 15] {
+        [
+            params.can_manage_chat,
+            params.can_post_messages,
+            params.can_edit_messages,
+            params.can_delete_messages,
+            params.can_post_stories,
+            params.can_edit_stories,
+            params.can_delete_stories,
+            params.can_manage_video_chats,
+            params.can_restrict_members,
+            params.can_promote_members,
+            params.can_change_info,
+            params.can_invite_users,
+            params.can_pin_messages,
+            params.can_manage_topics,
+            params.can_manage_direct_messages,
+        ]
+    }
+
+    #[test]
+    fn web_app_query_rejects_game_result() {
+        use crate::inline_mode::InlineQueryResultGame;
+
+        let params = AnswerWebAppQueryParams::builder()
+            .web_app_query_id("query")
+            .result(InlineQueryResult::Game(
+                InlineQueryResultGame::builder()
+                    .id("1")
+                    .game_short_name("game")
+                    .build(),
+            ))
+            .build();
+        let error = params.validate().unwrap_err();
+        assert_eq!(error.field, "result");
+    }
+
+    #[test]
+    fn web_app_query_rejects_empty_query_id() {
+        use crate::inline_mode::{InlineQueryResultArticle, InputTextMessageContent};
+
+        let article = InlineQueryResultArticle::builder()
+            .id("1")
+            .title("Title")
+            .input_message_content(
+                InputTextMessageContent::builder()
+                    .message_text("text")
+                    .build(),
+            )
+            .build();
+        let mut params = AnswerWebAppQueryParams::builder()
+            .web_app_query_id(" ")
+            .result(article.into())
+            .build();
+        assert_eq!(params.validate().unwrap_err().field, "web_app_query_id");
+
+        params.web_app_query_id = "query".to_string();
+        assert_eq!(params.validate(), Ok(()));
+    }
+
+    #[test]
+    fn web_app_query_result_gets_generated_id() {
+        use crate::inline_mode::{InlineQueryResultArticle, InputTextMessageContent};
+
+        let article = InlineQueryResultArticle::builder()
+            .id("")
+            .title("Title")
+            .input_message_content(
+                InputTextMessageContent::builder()
+                    .message_text("text")
+                    .build(),
+            )
+            .build();
+        let params = AnswerWebAppQueryParams::new("query", article.clone()).unwrap();
+        let id = params.result.id();
+        assert_eq!(id.len(), 36);
+        assert_eq!(id.as_bytes()[14], b'4');
+        assert_ne!(
+            id,
+            AnswerWebAppQueryParams::new("query", article.clone())
+                .unwrap()
+                .result
+                .id()
+        );
+
+        let article = InlineQueryResultArticle {
+            id: "own".to_string(),
+            ..article
+        };
+        let params = AnswerWebAppQueryParams::new("query", article.clone()).unwrap();
+        assert_eq!(params.result.id(), "own");
+
+        let error = AnswerWebAppQueryParams::new("", article).unwrap_err();
+        assert_eq!(error.field, "web_app_query_id");
+    }
+
+    #[test]
+    fn premium_gift_star_count_matches_months() {
+        let params = GiftPremiumSubscriptionParams::new(1, PremiumMonths::Six);
+        assert_eq!(params.month_count, 6);
+        assert_eq!(params.star_count, 1500);
+        assert_eq!(params.validate(), Ok(()));
+
+        let wrong_price = GiftPremiumSubscriptionParams {
+            star_count: 1000,
+            ..params.clone()
+        };
+        assert_eq!(wrong_price.validate().unwrap_err().field, "star_count");
+
+        let wrong_months = GiftPremiumSubscriptionParams {
+            month_count: 4,
+            ..params.clone()
+        };
+        assert_eq!(wrong_months.validate().unwrap_err().field, "month_count");
+
+        let both_formattings = GiftPremiumSubscriptionParams {
+            text: Some("*Enjoy*".to_string()),
+            text_parse_mode: Some("MarkdownV2".to_string()),
+            text_entities: Some(Vec::new()),
+            ..params
+        };
+        assert_eq!(
+            both_formattings.validate().unwrap_err().field,
+            "text_entities"
+        );
+    }
+
+    #[test]
+    fn verification_description_length() {
+        let params = VerifyUserParams::with_description(1, "ü".repeat(70)).unwrap();
+        assert_eq!(
+            params.custom_description.map(|d| d.chars().count()),
+            Some(70)
+        );
+        assert_eq!(VerifyUserParams::new(1).validate(), Ok(()));
+
+        let error = VerifyUserParams::with_description(1, "x".repeat(71)).unwrap_err();
+        assert_eq!(error.field, "custom_description");
+        let error = VerifyChatParams::with_description(-100, "x".repeat(71)).unwrap_err();
+        assert_eq!(error.field, "custom_description");
+        assert!(VerifyChatParams::with_description("@channel".to_string(), "").is_ok());
+    }
+
+    #[test]
+    fn business_account_username_rules() {
+        let params = |username: &str| {
+            SetBusinessAccountUsernameParams::builder()
+                .business_connection_id("connection")
+                .username(username)
+                .build()
+        };
+        assert_eq!(params("shop_bot42").validate(), Ok(()));
+        assert_eq!(params("").validate(), Ok(()));
+
+        for invalid in [
+            "shop", "1shop", "_shop", "shop-bot", "@shopbot", "shop_", "ßhopbot",
+        ] {
+            let error = params(invalid).validate().unwrap_err();
+            assert_eq!(error.field, "username", "{invalid}");
+        }
+        assert!(params(&"a".repeat(33)).validate().is_err());
+        // Four bytes, but the character is the problem and not the length.
+        assert!(params("shü")
+            .validate()
+            .unwrap_err()
+            .reason
+            .contains("English letters"));
+    }
+
+    #[test]
+    fn business_account_name_lengths() {
+        let params = SetBusinessAccountNameParams::builder()
+            .business_connection_id("connection")
+            .first_name("Jane")
+            .last_name("x".repeat(64))
+            .build();
+        assert_eq!(params.validate(), Ok(()));
+
+        let too_long = SetBusinessAccountNameParams {
+            last_name: Some("x".repeat(65)),
+            ..params.clone()
+        };
+        assert_eq!(too_long.validate().unwrap_err().field, "last_name");
+        let empty = SetBusinessAccountNameParams {
+            first_name: String::new(),
+            ..params
+        };
+        assert_eq!(empty.validate().unwrap_err().field, "first_name");
+    }
+
+    #[test]
+    fn business_gift_settings_presets() {
+        let all = SetBusinessAccountGiftSettingsParams::accept_all("connection");
+        assert!(all.show_gift_button);
+        assert_eq!(all.accepted_gift_types, AcceptedGiftTypes::ALL);
+        crate::test_json::assert_json_str(
+            &all,
+            r#"{"business_connection_id":"connection","show_gift_button":true,"accepted_gift_types":{"unlimited_gifts":true,"limited_gifts":true,"unique_gifts":true,"premium_subscription":true}}"#,
+        );
+
+        let none = SetBusinessAccountGiftSettingsParams::accept_none("connection");
+        assert!(!none.show_gift_button);
+        assert_eq!(none.accepted_gift_types, AcceptedGiftTypes::NONE);
+
+        let premium_only = AcceptedGiftTypes::builder()
+            .unlimited_gifts(false)
+            .limited_gifts(false)
+            .unique_gifts(false)
+            .premium_subscription(true)
+            .build();
+        let params = SetBusinessAccountGiftSettingsParams::accepting("connection", premium_only);
+        assert!(params.show_gift_button);
+        assert!(params.accepted_gift_types.premium_subscription);
+        assert!(!params.accepted_gift_types.unique_gifts);
+    }
+
+    #[test]
+    fn poll_builder_checks_option_count() {
+        let poll = PollBuilder::new(1, "Question?").add_option("Yes");
+        assert_eq!(poll.clone().build().unwrap_err().field, "options");
+
+        let poll = poll.add_option("No").build().unwrap();
+        assert_eq!(poll.options.len(), 2);
+        assert_eq!(poll.type_field, None);
+
+        let too_many = (0..=SendPollParams::MAX_OPTIONS)
+            .fold(PollBuilder::new(1, "Question?"), |poll, index| {
+                poll.add_option(index.to_string())
+            });
+        assert_eq!(too_many.build().unwrap_err().field, "options");
+    }
+
+    #[test]
+    fn poll_builder_checks_quiz_answer() {
+        let poll = PollBuilder::new(1, "2 + 2?")
+            .add_option("3")
+            .add_option("4")
+            .anonymous(false);
+
+        let quiz = poll.clone().quiz(1, "Basic math").build().unwrap();
+        assert_eq!(quiz.type_field, Some(PollType::Quiz));
+        assert_eq!(quiz.correct_option_id, Some(1));
+        assert_eq!(quiz.explanation.as_deref(), Some("Basic math"));
+        assert_eq!(quiz.is_anonymous, Some(false));
+
+        let error = poll.quiz(2, "Out of range").build().unwrap_err();
+        assert_eq!(error.field, "correct_option_id");
+    }
+
+    #[test]
+    fn poll_builder_keeps_formatted_options() {
+        let poll = PollBuilder::new(1, "Favourite?")
+            .add_formatted_option(InputPollOption::markdown("*Tea*"))
+            .add_option("Coffee")
+            .build()
+            .unwrap();
+        assert_eq!(poll.options[0].text_parse_mode, Some(ParseMode::MarkdownV2));
+        assert_eq!(poll.options[1].text_parse_mode, None);
+    }
+
+    #[test]
+    fn star_payment_reversal_rejects_empty_charge_id() {
+        let error = RefundStarPaymentParams::new(1, "").unwrap_err();
+        assert_eq!(error.field, "telegram_payment_charge_id");
+        let error = EditUserStarSubscriptionParams::cancel(1, " ").unwrap_err();
+        assert_eq!(error.field, "telegram_payment_charge_id");
+
+        let refund = RefundStarPaymentParams::new(1, "charge").unwrap();
+        assert_eq!(refund.telegram_payment_charge_id, "charge");
+        let subscription = EditUserStarSubscriptionParams::reenable(1, "charge").unwrap();
+        assert!(!subscription.is_canceled);
+        assert!(
+            EditUserStarSubscriptionParams::cancel(1, "charge")
+                .unwrap()
+                .is_canceled
+        );
+    }
+
+    #[test]
+    fn send_gift_with_message() {
+        let params =
+            SendGiftParams::with_message(42, "gift", "<b>Congrats</b>", ParseMode::Html).unwrap();
+        assert_eq!(params.user_id, Some(42));
+        assert_eq!(params.gift_id, "gift");
+        assert_eq!(params.text.as_deref(), Some("<b>Congrats</b>"));
+        assert_eq!(params.text_parse_mode, Some(ParseMode::Html));
+
+        let error = SendGiftParams::with_message(42, "", "Hi", ParseMode::Html).unwrap_err();
+        assert_eq!(error.field, "gift_id");
+    }
+
+    #[test]
+    fn sticker_emoji_list_bounds() {
+        let mut params = SetStickerEmojiListParams::builder()
+            .sticker("sticker")
+            .emoji_list(vec![])
+            .build();
+        assert_eq!(params.validate().unwrap_err().field, "emoji_list");
+
+        params.emoji_list = vec!["😀".to_string(); SetStickerEmojiListParams::MAX_EMOJI];
+        assert_eq!(params.validate(), Ok(()));
+
+        params.emoji_list.push("😀".to_string());
+        assert_eq!(params.validate().unwrap_err().field, "emoji_list");
+    }
+
+    #[test]
+    fn sticker_keywords_bounds() {
+        let mut params = SetStickerKeywordsParams::builder()
+            .sticker("sticker")
+            .build();
+        assert_eq!(params.validate(), Ok(()));
+
+        // 16 keywords with 4 characters each are exactly 64 characters
+        params.keywords = Some(vec!["cäts".to_string(); 16]);
+        assert_eq!(params.validate(), Ok(()));
+
+        params.keywords.as_mut().unwrap().push("a".to_string());
+        assert_eq!(params.validate().unwrap_err().field, "keywords");
+
+        params.keywords = Some(vec![
+            "a".to_string();
+            SetStickerKeywordsParams::MAX_KEYWORDS + 1
+        ]);
+        assert_eq!(params.validate().unwrap_err().field, "keywords");
+    }
+
+    fn sticker_set(name: &str) -> CreateNewStickerSetParams {
+        let sticker = InputSticker::builder()
+            .sticker("file_id".to_string())
+            .format(StickerFormat::Static)
+            .emoji_list(vec!["😀".to_string()])
+            .build();
+        CreateNewStickerSetParams::builder()
+            .user_id(1)
+            .name(name)
+            .title("Cats")
+            .stickers(vec![sticker])
+            .build()
+    }
+
+    #[test]
+    fn sticker_set_name_needs_bot_suffix() {
+        assert_eq!(sticker_set("cats_by_my_bot").validate("@my_bot"), Ok(()));
+        assert_eq!(sticker_set("Cats_by_My_Bot").validate("my_bot"), Ok(()));
+
+        let error = sticker_set("cats_by_other_bot")
+            .validate("my_bot")
+            .unwrap_err();
+        assert_eq!(error.field, "name");
+        let error = sticker_set("cats").validate("my_bot").unwrap_err();
+        assert_eq!(error.field, "name");
+    }
+
+    #[test]
+    fn sticker_set_name_rejects_invalid_characters() {
+        for name in [
+            "cats-dogs_by_my_bot",
+            "1cats_by_my_bot",
+            "cats__by_my_bot",
+            "kätzchen_by_my_bot",
+        ] {
+            let error = sticker_set(name).validate("my_bot").unwrap_err();
+            assert_eq!(error.field, "name", "{name}");
+        }
+    }
+
+    #[test]
+    fn sticker_set_needs_stickers() {
+        let mut params = sticker_set("cats_by_my_bot");
+        params.stickers.clear();
+        assert_eq!(params.validate("my_bot").unwrap_err().field, "stickers");
+    }
+
+    #[test]
+    fn photo_story_with_link_area() {
+        use crate::types::{StoryAreaPosition, StoryAreaType};
+
+        let position = StoryAreaPosition::builder()
+            .x_percentage(50.0)
+            .y_percentage(50.0)
+            .width_percentage(20.0)
+            .height_percentage(10.0)
+            .rotation_angle(0.0)
+            .corner_radius_percentage(5.0)
+            .build();
+        let params = PostStoryParams::builder()
+            .business_connection_id("connection")
+            .content(InputStoryContent::photo_path("./story.jpg"))
+            .active_period(86400)
+            .build()
+            .add_area(StoryArea::link(position, "https://example.com"));
+
+        let InputStoryContent::Photo(photo) = &params.content else {
+            panic!("expected photo content");
+        };
+        assert_eq!(
+            photo.photo,
+            FileUpload::from(std::path::PathBuf::from("./story.jpg"))
+        );
+
+        let areas = params.areas.unwrap();
+        assert_eq!(areas.len(), 1);
+        assert!(matches!(
+            &areas[0].type_field,
+            StoryAreaType::Link(link) if link.url == "https://example.com"
+        ));
+    }
+
+    #[test]
+    fn gift_management_rejects_empty_ids() {
+        let error = ConvertGiftToStarsParams::new("connection", "").unwrap_err();
+        assert_eq!(error.field, "owner_gift_id");
+        let error = ConvertGiftToStarsParams::new("", "gift").unwrap_err();
+        assert_eq!(error.field, "business_connection_id");
+        let error = TransferGiftParams::new("connection", " ", 1, None).unwrap_err();
+        assert_eq!(error.field, "owner_gift_id");
+
+        let params = TransferGiftParams::new("connection", "gift", 1, Some(25)).unwrap();
+        assert_eq!(params.star_count, Some(25));
+    }
+
+    #[test]
+    fn transfer_gift_pays_required_fee() {
+        let gift = |owned_gift_id: Option<&str>, can_be_transferred: bool| -> OwnedGiftUnique {
+            let sticker = serde_json::json!({
+                "file_id": "file",
+                "file_unique_id": "unique",
+                "type": "regular",
+                "width": 512,
+                "height": 512,
+                "is_animated": false,
+                "is_video": false
+            });
+            serde_json::from_value(serde_json::json!({
+                "gift": {
+                    "base_name": "Cake",
+                    "name": "Cake-1",
+                    "number": 1,
+                    "model": {"name": "model", "sticker": sticker, "rarity_per_mille": 10},
+                    "symbol": {"name": "symbol", "sticker": sticker, "rarity_per_mille": 10},
+                    "backdrop": {
+                        "name": "backdrop",
+                        "colors": {
+                            "center_color": 0,
+                            "edge_color": 0,
+                            "symbol_color": 0,
+                            "text_color": 0
+                        },
+                        "rarity_per_mille": 10
+                    }
+                },
+                "owned_gift_id": owned_gift_id,
+                "can_be_transferred": can_be_transferred,
+                "transfer_star_count": 25
+            }))
+            .unwrap()
+        };
+
+        let params =
+            TransferGiftParams::for_gift("connection", &gift(Some("gift"), true), 1).unwrap();
+        assert_eq!(params.owner_gift_id, "gift");
+        assert_eq!(params.star_count, Some(25));
+
+        let error = TransferGiftParams::for_gift("connection", &gift(None, true), 1).unwrap_err();
+        assert_eq!(error.field, "owner_gift_id");
+        let error =
+            TransferGiftParams::for_gift("connection", &gift(Some("gift"), false), 1).unwrap_err();
+        assert_eq!(error.field, "owner_gift_id");
+    }
+
+    #[test]
+    fn upgrade_gift_flags() {
+        let params = UpgradeGiftParams::new("connection", "gift");
+        assert_eq!(params.keep_original_details, None);
+        assert_eq!(params.star_count, None);
+
+        let params = params.keep_details().pay_stars(25);
+        assert_eq!(params.business_connection_id, "connection");
+        assert_eq!(params.owner_gift_id, "gift");
+        assert_eq!(params.keep_original_details, Some(true));
+        assert_eq!(params.star_count, Some(25));
+    }
+
+    #[test]
+    fn send_flags_set_notification_and_protection() {
+        let params = SendMessageParams::builder()
+            .chat_id(1)
+            .text("Hello")
+            .build();
+        assert_eq!(params.disable_notification, None);
+        assert_eq!(params.protect_content, None);
+
+        let params = params.silent().protected();
+        assert_eq!(params.disable_notification, Some(true));
+        assert_eq!(params.protect_content, Some(true));
+
+        let params = SendDiceParams::builder().chat_id(1).build().silent();
+        assert_eq!(params.disable_notification, Some(true));
+        assert_eq!(params.protect_content, None);
+    }
+
+    #[test]
+    fn send_paid_media_validation() {
+        use crate::input_media::InputPaidMediaPhoto;
+
+        let params = SendPaidMediaParams::new(1, 0);
+        assert_eq!(params.validate().unwrap_err().field, "star_count");
+
+        let params = SendPaidMediaParams::new(1, 50);
+        assert_eq!(params.validate().unwrap_err().field, "media");
+
+        let photo = InputPaidMediaPhoto::builder()
+            .media("file_id".to_string())
+            .build();
+        let params = params.add_media(photo.clone());
+        assert_eq!(params.validate(), Ok(()));
+        assert_eq!(params.media, [InputPaidMedia::Photo(photo.clone())]);
+
+        let params = (0..SendPaidMediaParams::MAX_MEDIA)
+            .fold(params, |params, _| params.add_media(photo.clone()));
+        assert_eq!(params.validate().unwrap_err().field, "media");
+    }
+
+    #[test]
+    fn clear_reactions_sends_empty_reaction_list() {
+        let params = SetMessageReactionParams::clear(-100_i64, 42);
+        assert!(params.reaction.is_empty());
+        crate::test_json::assert_json_str(
+            &params,
+            r#"{"chat_id":-100,"message_id":42,"reaction":[]}"#,
+        );
+    }
+
+    #[test]
+    fn answer_inline_query_with_buttons() {
+        let web_app = crate::types::WebAppInfo {
+            url: "https://example.com/settings".to_string(),
+        };
+        let params = AnswerInlineQueryParams::with_button(
+            "query",
+            Vec::new(),
+            InlineQueryResultsButton::web_app("Configure", web_app),
+        );
+        crate::test_json::assert_json_str(
+            &params,
+            r#"{"inline_query_id":"query","results":[],"button":{"text":"Configure","web_app":{"url":"https://example.com/settings"}}}"#,
+        );
+
+        let params = AnswerInlineQueryParams::with_button(
+            "query",
+            Vec::new(),
+            InlineQueryResultsButton::start_parameter("Log in", "login"),
+        );
+        crate::test_json::assert_json_str(
+            &params,
+            r#"{"inline_query_id":"query","results":[],"button":{"text":"Log in","start_parameter":"login"}}"#,
+        );
+    }
+
+    #[test]
+    fn xtr_invoice_link_validation() {
+        let params = CreateInvoiceLinkParams::builder()
+            .title("Stars")
+            .description("Premium access")
+            .payload("premium")
+            .currency(Currency::Xtr)
+            .prices(vec![LabeledPrice {
+                label: "Access".to_string(),
+                amount: 50,
+            }])
+            .subscription_period(2_592_000)
+            .build();
+        assert_eq!(params.validate(), Ok(()));
+
+        let with_token = CreateInvoiceLinkParams {
+            provider_token: Some("123:TEST".to_string()),
+            ..params.clone()
+        };
+        assert_eq!(with_token.validate().unwrap_err().field, "provider_token");
+
+        let empty_token = CreateInvoiceLinkParams {
+            provider_token: Some(String::new()),
+            ..params.clone()
+        };
+        assert_eq!(empty_token.validate(), Ok(()));
+
+        let without_prices = CreateInvoiceLinkParams {
+            prices: Vec::new(),
+            ..params.clone()
+        };
+        assert_eq!(without_prices.validate().unwrap_err().field, "prices");
+
+        let usd_subscription = CreateInvoiceLinkParams {
+            currency: Currency::Usd,
+            ..params
+        };
+        assert_eq!(
+            usd_subscription.validate().unwrap_err().field,
+            "subscription_period"
+        );
+    }
+
+    #[test]
+    fn video_note_validation() {
+        let params = SendVideoNoteParams::builder()
+            .chat_id(ChatId::Integer(1))
+            .video_note(InputFile::memory_mp4(vec![0; 4]))
+            .build();
+        assert_eq!(params.validate().unwrap_err().field, "length");
+
+        let params = SendVideoNoteParams {
+            length: Some(384),
+            duration: Some(30),
+            ..params
+        };
+        assert_eq!(params.validate(), Ok(()));
+
+        let too_long = SendVideoNoteParams {
+            duration: Some(61),
+            ..params.clone()
+        };
+        assert_eq!(too_long.validate().unwrap_err().field, "duration");
+
+        let too_big = SendVideoNoteParams {
+            length: Some(641),
+            ..params.clone()
+        };
+        assert_eq!(too_big.validate().unwrap_err().field, "length");
+
+        let file_id = SendVideoNoteParams {
+            video_note: FileUpload::String("file_id".to_string()),
+            length: None,
+            ..params
+        };
+        assert_eq!(file_id.validate(), Ok(()));
+    }
+
+    #[test]
+    fn commands_builder_validates_commands() {
+        let commands = CommandsBuilder::new()
+            .command("start", "Start the bot")
+            .command("set_language2", "Change the language")
+            .build()
+            .unwrap();
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[1].command, "set_language2");
+
+        let error = CommandsBuilder::new()
+            .command("my command", "Has a space")
+            .build()
+            .unwrap_err();
+        assert_eq!(error.field, "commands");
+        assert!(error.reason.contains("\"my command\""));
+
+        assert!(CommandsBuilder::new()
+            .command("Start", "Uppercase")
+            .build()
+            .is_err());
+        assert!(CommandsBuilder::new()
+            .command("start", "Go")
+            .build()
+            .is_err());
+        assert!(CommandsBuilder::new()
+            .command("a".repeat(33), "Too long")
+            .build()
+            .is_err());
+
+        let too_many = (0..=100).fold(CommandsBuilder::new(), |builder, index| {
+            builder.command(format!("command{index}"), "Description")
+        });
+        let error = too_many.build().unwrap_err();
+        assert!(error.reason.contains("got 101"));
+    }
+
+    #[test]
+    fn clear_reply_markup_sends_empty_keyboard() {
+        let params = EditMessageReplyMarkupParams::clear(-100, 42);
+        crate::test_json::assert_json_str(
+            &params,
+            r#"{"chat_id":-100,"message_id":42,"reply_markup":{"inline_keyboard":[]}}"#,
+        );
+    }
+
+    #[test]
+    fn vcard_is_escaped_and_round_trips_phone() {
+        let vcard = VCard::new("Jane")
+            .last_name("Doe")
+            .phone("+1 (555) 010-0000")
+            .email("jane@example.com")
+            .organization("Doe, Smith; Partners\nLLC");
+        assert_eq!(
+            vcard.to_string(),
+            "BEGIN:VCARD\r\nVERSION:3.0\r\nN:Doe;Jane;;;\r\nFN:Jane Doe\r\nTEL;TYPE=CELL:+1 (555) 010-0000\r\nEMAIL;TYPE=INTERNET:jane@example.com\r\nORG:Doe\\, Smith\\; Partners\\nLLC\r\nEND:VCARD"
+        );
+
+        let params = SendContactParams::from_vcard(1, &vcard).unwrap();
+        assert_eq!(params.phone_number, "+1 (555) 010-0000");
+        assert_eq!(params.last_name.as_deref(), Some("Doe"));
+        assert_eq!(
+            VCard::phone_numbers(params.vcard.as_deref().unwrap()),
+            ["+1 (555) 010-0000"]
+        );
+
+        let error = SendContactParams::from_vcard(1, &VCard::new("Jane")).unwrap_err();
+        assert_eq!(error.field, "phone_number");
+    }
+
+    #[test]
+    fn music_sets_audio_metadata() {
+        let params = SendAudioParams::music(1, "file-id".to_string(), "Daft Punk", "One More Time");
+        assert_eq!(params.performer.as_deref(), Some("Daft Punk"));
+        assert_eq!(params.title.as_deref(), Some("One More Time"));
+        crate::test_json::assert_json_str(
+            &SendAudioParams {
+                duration: Some(320),
+                ..params
+            },
+            r#"{"chat_id":1,"audio":"file-id","duration":320,"performer":"Daft Punk","title":"One More Time"}"#,
+        );
+    }
+
+    #[test]
+    fn venue_with_required_fields() {
+        let venue = SendVenueParams::new(1, 52.5163, 13.3777, "Brandenburg Gate", "Pariser Platz");
+        crate::test_json::assert_json_str(
+            &venue,
+            r#"{"chat_id":1,"latitude":52.5163,"longitude":13.3777,"title":"Brandenburg Gate","address":"Pariser Platz"}"#,
+        );
+
+        let venue = venue
+            .foursquare("4adcda10f964a520af3521e3", None)
+            .google_place(
+                "ChIJiQnyVcZRqEcRY0xnhE77uyY",
+                Some("tourist_attraction".to_string()),
+            );
+        assert_eq!(
+            venue.foursquare_id.as_deref(),
+            Some("4adcda10f964a520af3521e3")
+        );
+        assert_eq!(venue.foursquare_type, None);
+        assert_eq!(
+            venue.google_place_type.as_deref(),
+            Some("tourist_attraction")
+        );
+    }
+
+    #[test]
+    fn set_game_score_addressing_and_flags() {
+        let params = SetGameScoreParams::for_message(1, 100, -100, 42).force();
+        assert_eq!(params.validate(), Ok(()));
+        crate::test_json::assert_json_str(
+            &params,
+            r#"{"user_id":1,"score":100,"force":true,"chat_id":-100,"message_id":42}"#,
+        );
+
+        let params = SetGameScoreParams::for_inline_message(1, 100, "inline").no_edit();
+        assert_eq!(params.validate(), Ok(()));
+        crate::test_json::assert_json_str(
+            &params,
+            r#"{"user_id":1,"score":100,"disable_edit_message":true,"inline_message_id":"inline"}"#,
+        );
+
+        let both = SetGameScoreParams {
+            chat_id: Some(-100),
+            message_id: Some(42),
+            ..params.clone()
+        };
+        assert_eq!(both.validate().unwrap_err().field, "inline_message_id");
+
+        let missing_message = SetGameScoreParams {
+            chat_id: Some(-100),
+            inline_message_id: None,
+            ..params
+        };
+        assert_eq!(missing_message.validate().unwrap_err().field, "message_id");
+    }
+
+    #[test]
+    fn forward_messages_validation() {
+        let params = ForwardMessagesParams::builder()
+            .chat_id(ChatId::Integer(1))
+            .from_chat_id(ChatId::Integer(2))
+            .message_ids(vec![1, 2, 5])
+            .build();
+        assert_eq!(params.validate(), Ok(()));
+
+        let empty = ForwardMessagesParams {
+            message_ids: Vec::new(),
+            ..params.clone()
+        };
+        assert!(empty.validate().is_err());
+        let too_many = ForwardMessagesParams {
+            message_ids: (1..=101).collect(),
+            ..params.clone()
+        };
+        assert!(too_many.validate().is_err());
+        let unsorted = ForwardMessagesParams {
+            message_ids: vec![2, 1],
+            ..params
+        };
+        assert!(unsorted.validate().is_err());
+    }
+
+    #[test]
+    fn copy_messages_caption_flag() {
+        let params = CopyMessagesParams::without_captions(-100, 42, vec![1, 2, 3]);
+        assert_eq!(params.remove_caption, Some(true));
+        assert_eq!(params.from_chat_id, ChatId::Integer(-100));
+        assert_eq!(params.chat_id, ChatId::Integer(42));
+        crate::test_json::assert_json_str(
+            &params,
+            r#"{"chat_id":42,"from_chat_id":-100,"message_ids":[1,2,3],"remove_caption":true}"#,
+        );
+
+        let params = params.keep_captions();
+        assert_eq!(params.remove_caption, None);
+        assert_eq!(params.remove_captions().remove_caption, Some(true));
+    }
+
+    #[test]
+    fn invite_link_builder() {
+        let params = InviteLinkBuilder::new()
+            .name("Friends")
+            .expire_date(1_700_000_000)
+            .member_limit(10)
+            .build_create(-100)
+            .unwrap();
+        crate::test_json::assert_json_str(
+            &params,
+            r#"{"chat_id":-100,"name":"Friends","expire_date":1700000000,"member_limit":10}"#,
+        );
+
+        let params = InviteLinkBuilder::new()
+            .creates_join_request()
+            .build_edit(-100, "https://t.me/+abc")
+            .unwrap();
+        assert_eq!(params.creates_join_request, Some(true));
+        assert_eq!(params.member_limit, None);
+
+        let error = InviteLinkBuilder::new()
+            .member_limit(10)
+            .creates_join_request()
+            .build_create(-100)
+            .unwrap_err();
+        assert_eq!(error.field, "member_limit");
+        assert!(InviteLinkBuilder::new().member_limit(0).validate().is_err());
+        assert!(InviteLinkBuilder::new()
+            .name("x".repeat(33))
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn invite_link_expiry_from_duration() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        assert_eq!(
+            expire_date_after(now, Duration::from_secs(3600)),
+            1_700_003_600
+        );
+        assert_eq!(
+            expire_date_after(now + Duration::from_millis(1), Duration::from_secs(60)),
+            1_700_000_061
+        );
+
+        let before = expire_date_after(SystemTime::now(), Duration::from_secs(86_400));
+        let params = InviteLinkBuilder::new()
+            .expires_in(Duration::from_secs(86_400))
+            .build_create(-100)
+            .unwrap();
+        let expire_date = params.expire_date.unwrap();
+        assert!(expire_date >= before && expire_date <= before + 1);
+    }
+
+    #[test]
+    fn restriction_until_date_from_duration() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let restrict = RestrictChatMemberParams::builder()
+            .chat_id(-100)
+            .user_id(1)
+            .permissions(ChatPermissions::builder().can_send_messages(false).build())
+            .build()
+            .restrict_for_at(now, Duration::from_secs(3600));
+        assert_eq!(restrict.until_date, Some(1_700_003_600));
+
+        let ban = BanChatMemberParams::builder()
+            .chat_id(-100)
+            .user_id(1)
+            .build()
+            .ban_for_at(now, Duration::from_secs(3600));
+        assert_eq!(ban.until_date, Some(1_700_003_600));
+    }
+
+    #[test]
+    fn emoji_status_expiration_from_duration() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let params = SetUserEmojiStatusParams::new(1, "emoji")
+            .expires_in_at(now, Duration::from_secs(2 * 60 * 60));
+        crate::test_json::assert_json_str(
+            &params,
+            r#"{"user_id":1,"emoji_status_custom_emoji_id":"emoji","emoji_status_expiration_date":1700007200}"#,
+        );
+
+        crate::test_json::assert_json_str(
+            &SetUserEmojiStatusParams::clear(1),
+            r#"{"user_id":1,"emoji_status_custom_emoji_id":""}"#,
+        );
+    }
+
+    #[test]
+    fn subscription_invite_link_params() {
+        let params = CreateChatSubscriptionInviteLinkParams::new(-100, 250).unwrap();
+        crate::test_json::assert_json_str(
+            &params,
+            r#"{"chat_id":-100,"subscription_period":2592000,"subscription_price":250}"#,
+        );
+
+        for price in [0, 10_001] {
+            let error = CreateChatSubscriptionInviteLinkParams::new(-100, price).unwrap_err();
+            assert_eq!(error.field, "subscription_price");
+        }
+        let weekly = CreateChatSubscriptionInviteLinkParams {
+            subscription_period: 604_800,
+            ..params
+        };
+        assert_eq!(weekly.validate().unwrap_err().field, "subscription_period");
+    }
+
+    #[test]
+    fn answer_callback_query_url_and_cache_time() {
+        let query: CallbackQuery = serde_json::from_str(
+            r#"{"id":"42","from":{"id":1,"is_bot":false,"first_name":"A"},"chat_instance":"1"}"#,
+        )
+        .unwrap();
+        let game_query = CallbackQuery {
+            game_short_name: Some("tetris".to_string()),
+            ..query.clone()
+        };
+
+        let params = AnswerCallbackQueryParams::toast("42", "Saved").cache_time(30);
+        crate::test_json::assert_json_str(
+            &params,
+            r#"{"callback_query_id":"42","text":"Saved","cache_time":30}"#,
+        );
+        assert_eq!(params.validate_for(&query), Ok(()));
+
+        let alert = AnswerCallbackQueryParams::alert("42", "Nope");
+        assert_eq!(alert.show_alert, Some(true));
+
+        let game = AnswerCallbackQueryParams::builder()
+            .callback_query_id("42")
+            .build()
+            .url("https://example.com/tetris");
+        assert_eq!(game.url.as_deref(), Some("https://example.com/tetris"));
+        assert_eq!(game.validate_for(&game_query), Ok(()));
+        assert_eq!(game.validate_for(&query).unwrap_err().field, "url");
+
+        let deep_link = game.url("https://t.me/my_bot?start=promo");
+        assert_eq!(deep_link.validate_for(&query), Ok(()));
+        assert!(deep_link
+            .clone()
+            .url("https://t.me/my_bot")
+            .validate_for(&query)
+            .is_err());
+
+        let other_query = CallbackQuery {
+            id: "43".to_string(),
+            ..query
+        };
+        assert_eq!(
+            deep_link.validate_for(&other_query).unwrap_err().field,
+            "callback_query_id"
+        );
+    }
+
+    #[test]
+    fn parse_mode_and_entities_are_exclusive() {
+        let bold = MessageEntity::builder()
+            .type_field(crate::types::MessageEntityType::Bold)
+            .offset(0)
+            .length(5)
+            .build();
+        let params = SendMessageParams::builder()
+            .chat_id(1)
+            .text("Hello")
+            .build();
+        assert_eq!(params.validate(), Ok(()));
+
+        let with_parse_mode = SendMessageParams {
+            parse_mode: Some(ParseMode::Html),
+            ..params.clone()
+        };
+        assert_eq!(with_parse_mode.validate(), Ok(()));
+        let with_entities = SendMessageParams {
+            entities: Some(vec![bold.clone()]),
+            ..params
+        };
+        assert_eq!(with_entities.validate(), Ok(()));
+
+        let both = SendMessageParams {
+            parse_mode: Some(ParseMode::Html),
+            ..with_entities
+        };
+        assert_eq!(both.validate().unwrap_err().field, "entities");
+
+        let photo = SendPhotoParams::builder()
+            .chat_id(1)
+            .photo("file_id".to_string())
+            .caption("Hello")
+            .parse_mode(ParseMode::MarkdownV2)
+            .caption_entities(vec![bold])
+            .build();
+        assert_eq!(photo.validate().unwrap_err().field, "caption_entities");
+    }
+
+    #[test]
+    fn full_admin_grants_every_right() {
+        let params = PromoteChatMemberParams::full_admin(-100, 42);
+        assert_eq!(params.chat_id, ChatId::Integer(-100));
+        assert_eq!(params.user_id, 42);
+        assert_eq!(params.is_anonymous, None);
+        assert!(promote_rights(&params)
+            .iter()
+            .all(|right| *right == Some(true)));
+    }
+
+    #[test]
+    fn demote_revokes_every_right() {
+        let params = PromoteChatMemberParams::demote(-100, 42);
+        assert_eq!(params.is_anonymous, None);
+        assert!(promote_rights(&params)
+            .iter()
+            .all(|right| *right == Some(false)));
+    }
+}
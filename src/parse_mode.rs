@@ -41,6 +41,56 @@ impl ParseMode {
             Self::Markdown => "Markdown",
         }
     }
+
+    /// Escape `text` so that it is shown literally when sent with this parse mode.
+    #[must_use]
+    pub fn escape(self, text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            match self {
+                Self::Html => match c {
+                    '&' => escaped.push_str("&amp;"),
+                    '<' => escaped.push_str("&lt;"),
+                    '>' => escaped.push_str("&gt;"),
+                    '"' => escaped.push_str("&quot;"),
+                    _ => escaped.push(c),
+                },
+                Self::MarkdownV2 => {
+                    if matches!(
+                        c,
+                        '_' | '*'
+                            | '['
+                            | ']'
+                            | '('
+                            | ')'
+                            | '~'
+                            | '`'
+                            | '>'
+                            | '#'
+                            | '+'
+                            | '-'
+                            | '='
+                            | '|'
+                            | '{'
+                            | '}'
+                            | '.'
+                            | '!'
+                            | '\\'
+                    ) {
+                        escaped.push('\\');
+                    }
+                    escaped.push(c);
+                }
+                Self::Markdown => {
+                    if matches!(c, '_' | '*' | '`' | '[') {
+                        escaped.push('\\');
+                    }
+                    escaped.push(c);
+                }
+            }
+        }
+        escaped
+    }
 }
 
 impl Display for ParseMode {
@@ -58,3 +108,27 @@ fn serde_markdown_works() {
 fn serde_html_works() {
     crate::test_json::assert_json_str(&ParseMode::Html, r#""HTML""#);
 }
+
+#[test]
+fn escape_html() {
+    assert_eq!(
+        ParseMode::Html.escape(r#"<b>"Tom" & Jerry</b>"#),
+        "&lt;b&gt;&quot;Tom&quot; &amp; Jerry&lt;/b&gt;"
+    );
+}
+
+#[test]
+fn escape_markdown_v2() {
+    assert_eq!(
+        ParseMode::MarkdownV2.escape(r"*bold* [link](url) 1.5! a\b"),
+        r"\*bold\* \[link\]\(url\) 1\.5\! a\\b"
+    );
+}
+
+#[test]
+fn escape_markdown() {
+    assert_eq!(
+        ParseMode::Markdown.escape("_a_ *b* `c` [d]"),
+        r"\_a\_ \*b\* \`c\` \[d]"
+    );
+}
@@ -77,6 +77,136 @@ pub enum PassportElementError {
     Unspecified(PassportElementErrorUnspecified),
 }
 
+impl PassportElementError {
+    /// Error in a field of the data, `data_hash` is the base64-encoded hash of the data.
+    #[must_use]
+    pub fn data_field<F: Into<String>, H: Into<String>, M: Into<String>>(
+        type_field: PassportElementErrorDataFieldType,
+        field_name: F,
+        data_hash: H,
+        message: M,
+    ) -> Self {
+        Self::DataField(PassportElementErrorDataField {
+            type_field,
+            field_name: field_name.into(),
+            data_hash: data_hash.into(),
+            message: message.into(),
+        })
+    }
+
+    /// Problem with the front side of a document.
+    #[must_use]
+    pub fn front_side<H: Into<String>, M: Into<String>>(
+        type_field: PassportElementErrorFrontSideType,
+        file_hash: H,
+        message: M,
+    ) -> Self {
+        Self::FrontSide(PassportElementErrorFrontSide {
+            type_field,
+            file_hash: file_hash.into(),
+            message: message.into(),
+        })
+    }
+
+    /// Problem with the reverse side of a document.
+    #[must_use]
+    pub fn reverse_side<H: Into<String>, M: Into<String>>(
+        type_field: PassportElementErrorReverseSideType,
+        file_hash: H,
+        message: M,
+    ) -> Self {
+        Self::ReverseSide(PassportElementErrorReverseSide {
+            type_field,
+            file_hash: file_hash.into(),
+            message: message.into(),
+        })
+    }
+
+    /// Problem with the selfie with a document.
+    #[must_use]
+    pub fn selfie<H: Into<String>, M: Into<String>>(
+        type_field: PassportElementErrorSelfieType,
+        file_hash: H,
+        message: M,
+    ) -> Self {
+        Self::Selfie(PassportElementErrorSelfie {
+            type_field,
+            file_hash: file_hash.into(),
+            message: message.into(),
+        })
+    }
+
+    /// Problem with a document scan.
+    #[must_use]
+    pub fn file<H: Into<String>, M: Into<String>>(
+        type_field: PassportElementErrorFileType,
+        file_hash: H,
+        message: M,
+    ) -> Self {
+        Self::File(PassportElementErrorFile {
+            type_field,
+            file_hash: file_hash.into(),
+            message: message.into(),
+        })
+    }
+
+    /// Problem with a list of document scans.
+    #[must_use]
+    pub fn files<M: Into<String>>(
+        type_field: PassportElementErrorFileType,
+        file_hashes: Vec<String>,
+        message: M,
+    ) -> Self {
+        Self::Files(PassportElementErrorFiles {
+            type_field,
+            file_hashes,
+            message: message.into(),
+        })
+    }
+
+    /// Problem with one of the files of a document translation.
+    #[must_use]
+    pub fn translation_file<H: Into<String>, M: Into<String>>(
+        type_field: PassportElementErrorTranslationFileType,
+        file_hash: H,
+        message: M,
+    ) -> Self {
+        Self::TranslationFile(PassportElementErrorTranslationFile {
+            type_field,
+            file_hash: file_hash.into(),
+            message: message.into(),
+        })
+    }
+
+    /// Problem with the translated version of a document.
+    #[must_use]
+    pub fn translation_files<M: Into<String>>(
+        type_field: PassportElementErrorTranslationFileType,
+        file_hashes: Vec<String>,
+        message: M,
+    ) -> Self {
+        Self::TranslationFiles(PassportElementErrorTranslationFiles {
+            type_field,
+            file_hashes,
+            message: message.into(),
+        })
+    }
+
+    /// Problem with an element which isn't covered by the other errors, e.g. it has to be resent.
+    #[must_use]
+    pub fn unspecified<H: Into<String>, M: Into<String>>(
+        type_field: EncryptedPassportElementType,
+        element_hash: H,
+        message: M,
+    ) -> Self {
+        Self::Unspecified(PassportElementErrorUnspecified {
+            type_field,
+            element_hash: element_hash.into(),
+            message: message.into(),
+        })
+    }
+}
+
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum PassportElementErrorDataFieldType {
@@ -218,3 +348,50 @@ pub struct PassportElementErrorUnspecified {
     pub element_hash: String,
     pub message: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_field_error_json() {
+        let error = PassportElementError::data_field(
+            PassportElementErrorDataFieldType::PersonalDetails,
+            "first_name",
+            "aGFzaA==",
+            "The name doesn't match the document",
+        );
+        crate::test_json::assert_json_str(
+            &error,
+            r#"{"source":"data","type":"personal_details","field_name":"first_name","data_hash":"aGFzaA==","message":"The name doesn't match the document"}"#,
+        );
+    }
+
+    #[test]
+    fn file_errors_json() {
+        let error = PassportElementError::front_side(
+            PassportElementErrorFrontSideType::DriverLicense,
+            "ZnJvbnQ=",
+            "The scan is blurry",
+        );
+        crate::test_json::assert_json_str(
+            &error,
+            r#"{"source":"front_side","type":"driver_license","file_hash":"ZnJvbnQ=","message":"The scan is blurry"}"#,
+        );
+
+        let error = PassportElementError::files(
+            PassportElementErrorFileType::UtilityBill,
+            vec!["YQ==".to_string(), "Yg==".to_string()],
+            "The bills are too old",
+        );
+        crate::test_json::assert_json_str(
+            &error,
+            r#"{"source":"files","type":"utility_bill","file_hashes":["YQ==","Yg=="],"message":"The bills are too old"}"#,
+        );
+        let parsed: PassportElementError = serde_json::from_str(
+            r#"{"source":"files","type":"utility_bill","file_hashes":["YQ==","Yg=="],"message":"The bills are too old"}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed, error);
+    }
+}
@@ -2,10 +2,177 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::error::ValidationError;
 use crate::gifts::Gift;
 use crate::macros::{apistruct, apply};
 use crate::types::{Chat, PaidMedia, User};
 
+macro_rules! currencies {
+    ($($variant:ident = $code:literal, $exponent:literal;)+) => {
+        /// Three-letter ISO 4217 currency code used for payments.
+        ///
+        /// Unknown codes are kept as [`Currency::Other`] so newly supported currencies still work.
+        /// Build a currency from a code with [`Currency::from`], which picks the named variant for
+        /// known codes. `Currency::Other("USD".into())` does not equal [`Currency::Usd`].
+        ///
+        /// See <https://core.telegram.org/bots/payments#supported-currencies>.
+        #[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+        #[serde(from = "String", into = "String")]
+        pub enum Currency {
+            $(
+                #[doc = concat!("`", $code, "`")]
+                $variant,
+            )+
+            /// Telegram Stars, used for payments in digital goods and services.
+            Xtr,
+            /// A code without a named variant. Use [`Currency::from`] instead of building it directly.
+            Other(String),
+        }
+
+        impl Currency {
+            #[must_use]
+            pub fn code(&self) -> &str {
+                match self {
+                    $(Self::$variant => $code,)+
+                    Self::Xtr => "XTR",
+                    Self::Other(code) => code,
+                }
+            }
+
+            /// Number of digits past the decimal point of the smallest unit of the currency.
+            ///
+            /// For example `2` for USD where amounts are given in cents. `None` for [`Currency::Other`].
+            #[must_use]
+            pub const fn exponent(&self) -> Option<u32> {
+                match self {
+                    $(Self::$variant => Some($exponent),)+
+                    Self::Xtr => Some(0),
+                    Self::Other(_) => None,
+                }
+            }
+        }
+
+        impl From<&str> for Currency {
+            fn from(code: &str) -> Self {
+                match code.to_ascii_uppercase().as_str() {
+                    $($code => Self::$variant,)+
+                    "XTR" => Self::Xtr,
+                    _ => Self::Other(code.to_owned()),
+                }
+            }
+        }
+    };
+}
+
+currencies! {
+    Aed = "AED", 2;
+    Afn = "AFN", 2;
+    All = "ALL", 2;
+    Amd = "AMD", 2;
+    Ars = "ARS", 2;
+    Aud = "AUD", 2;
+    Azn = "AZN", 2;
+    Bam = "BAM", 2;
+    Bdt = "BDT", 2;
+    Bgn = "BGN", 2;
+    Bnd = "BND", 2;
+    Bob = "BOB", 2;
+    Brl = "BRL", 2;
+    Byn = "BYN", 2;
+    Cad = "CAD", 2;
+    Chf = "CHF", 2;
+    Clp = "CLP", 0;
+    Cny = "CNY", 2;
+    Cop = "COP", 2;
+    Crc = "CRC", 2;
+    Czk = "CZK", 2;
+    Dkk = "DKK", 2;
+    Dop = "DOP", 2;
+    Dzd = "DZD", 2;
+    Egp = "EGP", 2;
+    Etb = "ETB", 2;
+    Eur = "EUR", 2;
+    Gbp = "GBP", 2;
+    Gel = "GEL", 2;
+    Gtq = "GTQ", 2;
+    Hkd = "HKD", 2;
+    Hnl = "HNL", 2;
+    Huf = "HUF", 2;
+    Idr = "IDR", 2;
+    Ils = "ILS", 2;
+    Inr = "INR", 2;
+    Isk = "ISK", 0;
+    Jmd = "JMD", 2;
+    Jpy = "JPY", 0;
+    Kes = "KES", 2;
+    Kgs = "KGS", 2;
+    Krw = "KRW", 0;
+    Kzt = "KZT", 2;
+    Lbp = "LBP", 2;
+    Lkr = "LKR", 2;
+    Mad = "MAD", 2;
+    Mdl = "MDL", 2;
+    Mnt = "MNT", 2;
+    Mur = "MUR", 2;
+    Mvr = "MVR", 2;
+    Mxn = "MXN", 2;
+    Myr = "MYR", 2;
+    Mzn = "MZN", 2;
+    Ngn = "NGN", 2;
+    Nio = "NIO", 2;
+    Nok = "NOK", 2;
+    Npr = "NPR", 2;
+    Nzd = "NZD", 2;
+    Pab = "PAB", 2;
+    Pen = "PEN", 2;
+    Php = "PHP", 2;
+    Pkr = "PKR", 2;
+    Pln = "PLN", 2;
+    Pyg = "PYG", 0;
+    Qar = "QAR", 2;
+    Ron = "RON", 2;
+    Rsd = "RSD", 2;
+    Rub = "RUB", 2;
+    Sar = "SAR", 2;
+    Sek = "SEK", 2;
+    Sgd = "SGD", 2;
+    Thb = "THB", 2;
+    Tjs = "TJS", 2;
+    Try = "TRY", 2;
+    Ttd = "TTD", 2;
+    Twd = "TWD", 2;
+    Tzs = "TZS", 2;
+    Uah = "UAH", 2;
+    Ugx = "UGX", 0;
+    Usd = "USD", 2;
+    Uyu = "UYU", 2;
+    Uzs = "UZS", 2;
+    Vnd = "VND", 0;
+    Yer = "YER", 2;
+    Zar = "ZAR", 2;
+}
+
+impl From<String> for Currency {
+    fn from(code: String) -> Self {
+        Self::from(code.as_str())
+    }
+}
+
+impl From<Currency> for String {
+    fn from(currency: Currency) -> Self {
+        match currency {
+            Currency::Other(code) => code,
+            known => known.code().to_owned(),
+        }
+    }
+}
+
+impl std::fmt::Display for Currency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct LabeledPrice {
@@ -13,6 +180,64 @@ pub struct LabeledPrice {
     pub amount: u32,
 }
 
+impl LabeledPrice {
+    /// Creates a price from a decimal `amount` like `"1.50"` in the main unit of `currency`.
+    ///
+    /// The amount is converted to the smallest unit of the currency, e.g. `"1.50"` USD becomes
+    /// 150 cents. Amounts with more decimal places than the currency allows are rejected instead
+    /// of being rounded.
+    pub fn from_decimal<L: Into<String>>(
+        label: L,
+        amount: &str,
+        currency: &Currency,
+    ) -> Result<Self, ValidationError> {
+        let Some(exponent) = currency.exponent() else {
+            return Err(ValidationError::new(
+                "currency",
+                format!("unknown exponent for currency {currency}"),
+            ));
+        };
+        let invalid =
+            || ValidationError::new("amount", format!("{amount:?} is not a decimal amount"));
+
+        let amount = amount.trim();
+        let (integer, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+        if integer.is_empty() && fraction.is_empty() {
+            return Err(invalid());
+        }
+        if !integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|byte| byte.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.len() > exponent as usize {
+            return Err(ValidationError::new(
+                "amount",
+                format!("{currency} allows at most {exponent} decimal places, got {amount}"),
+            ));
+        }
+
+        let too_big = || ValidationError::new("amount", format!("{amount} {currency} is too big"));
+        let mut total: u32 = 0;
+        let padding = std::iter::repeat(b'0').take(exponent as usize - fraction.len());
+        for digit in integer.bytes().chain(fraction.bytes()).chain(padding) {
+            total = total
+                .checked_mul(10)
+                .and_then(|total| total.checked_add(u32::from(digit - b'0')))
+                .ok_or_else(too_big)?;
+        }
+
+        Ok(Self {
+            label: label.into(),
+            amount: total,
+        })
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct Invoice {
@@ -227,3 +452,95 @@ pub struct StarAmount {
     pub amount: i32,
     pub nanostar_amount: Option<i32>,
 }
+
+/// Detects when the bot's Star balance drops below a threshold.
+///
+/// Only the crossing is reported: after [`check`](Self::check) returned `true` it stays `false`
+/// until the balance went back to the threshold or above, so a low balance doesn't alert on
+/// every poll.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StarBalanceAlert {
+    threshold: u32,
+    below: bool,
+}
+
+impl StarBalanceAlert {
+    #[must_use]
+    pub const fn new(threshold: u32) -> Self {
+        Self {
+            threshold,
+            below: false,
+        }
+    }
+
+    #[must_use]
+    pub const fn threshold(self) -> u32 {
+        self.threshold
+    }
+
+    /// Records the current `balance` and returns whether it just crossed below the threshold.
+    pub const fn check(&mut self, balance: u32) -> bool {
+        let below = balance < self.threshold;
+        let crossed = below && !self.below;
+        self.below = below;
+        crossed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn currency_exponents() {
+        assert_eq!(Currency::Usd.exponent(), Some(2));
+        assert_eq!(Currency::Eur.exponent(), Some(2));
+        assert_eq!(Currency::Jpy.exponent(), Some(0));
+        assert_eq!(Currency::Xtr.exponent(), Some(0));
+        assert_eq!(Currency::from("ABC").exponent(), None);
+    }
+
+    #[test]
+    fn currency_serde_keeps_unknown_codes() {
+        crate::test_json::assert_json_str(&Currency::Usd, r#""USD""#);
+        crate::test_json::assert_json_str(&Currency::Xtr, r#""XTR""#);
+        assert_eq!(Currency::from("usd"), Currency::Usd);
+
+        let currency: Currency = serde_json::from_str(r#""ABC""#).unwrap();
+        assert_eq!(currency, Currency::Other("ABC".to_string()));
+        crate::test_json::assert_json_str(&currency, r#""ABC""#);
+    }
+
+    #[test]
+    fn labeled_price_from_decimal() {
+        let price = LabeledPrice::from_decimal("Coffee", "1.50", &Currency::Usd).unwrap();
+        assert_eq!(price.amount, 150);
+        assert_eq!(price.label, "Coffee");
+
+        let price = LabeledPrice::from_decimal("Coffee", "500", &Currency::Jpy).unwrap();
+        assert_eq!(price.amount, 500);
+        let price = LabeledPrice::from_decimal("Coffee", "2.500", &Currency::Usd).unwrap();
+        assert_eq!(price.amount, 250);
+    }
+
+    #[test]
+    fn labeled_price_from_decimal_rejects_too_precise_amounts() {
+        let error = LabeledPrice::from_decimal("Coffee", "500.5", &Currency::Jpy).unwrap_err();
+        assert_eq!(error.field, "amount");
+        assert!(LabeledPrice::from_decimal("Coffee", "1.505", &Currency::Usd).is_err());
+        assert!(LabeledPrice::from_decimal("Coffee", "-1", &Currency::Usd).is_err());
+        assert!(LabeledPrice::from_decimal("Coffee", ".", &Currency::Usd).is_err());
+        assert!(LabeledPrice::from_decimal("Coffee", "1", &Currency::from("ABC")).is_err());
+        assert!(LabeledPrice::from_decimal("Coffee", "50000000", &Currency::Usd).is_err());
+    }
+
+    #[test]
+    fn star_balance_alert_fires_once_per_crossing() {
+        let mut alert = StarBalanceAlert::new(100);
+        let fired: Vec<u32> = [150, 120, 100, 99, 80, 50, 100, 120, 90, 10]
+            .into_iter()
+            .filter(|balance| alert.check(*balance))
+            .collect();
+        assert_eq!(fired, [99, 90]);
+    }
+}
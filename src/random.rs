@@ -0,0 +1,10 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+/// Random number from the randomly seeded std hasher, good enough for ids and boundaries but not
+/// for anything security related.
+pub fn random_u128() -> u128 {
+    let high = RandomState::new().build_hasher().finish();
+    let low = RandomState::new().build_hasher().finish();
+    (u128::from(high) << 64) | u128::from(low)
+}
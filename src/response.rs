@@ -4,7 +4,8 @@
 
 #![allow(clippy::module_name_repetitions)]
 
-use serde::{Deserialize, Serialize};
+use serde::de::value::{BoolDeserializer, UnitDeserializer};
+use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::types::Message;
 
@@ -14,14 +15,86 @@ use crate::types::Message;
 /// Some methods may return a human-readable `description` of the result.
 /// The result of the query can be found in the 'result' field.
 ///
+/// A missing or `null` `result` is accepted for results which can be empty, like `Option<T>`, and
+/// read as `true` for boolean results of successful requests. Otherwise deserialization fails
+/// with an error naming the missing `result`.
+///
 /// See <https://core.telegram.org/bots/api#making-requests>
 #[serde_with::skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, PartialEq, Eq)]
 pub struct MethodResponse<T> {
-    /// Always true
+    /// Always true for responses returned by the clients, see [`into_result`](Self::into_result)
     pub ok: bool,
     pub description: Option<String>,
     pub result: T,
+    /// Only present when `ok` is false
+    pub error_code: Option<u64>,
+    /// Only present when `ok` is false
+    pub parameters: Option<ResponseParameters>,
+}
+
+impl<T> MethodResponse<T> {
+    /// Returns the `result` when `ok` is true and the error details otherwise.
+    ///
+    /// Missing error details are filled with an empty `description` and an `error_code` of 0.
+    pub fn into_result(self) -> Result<T, ErrorResponse> {
+        if self.ok {
+            Ok(self.result)
+        } else {
+            Err(ErrorResponse {
+                ok: false,
+                description: self.description.unwrap_or_default(),
+                error_code: self.error_code.unwrap_or_default(),
+                parameters: self.parameters,
+            })
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for MethodResponse<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Raw<T> {
+            ok: bool,
+            description: Option<String>,
+            result: Option<T>,
+            error_code: Option<u64>,
+            parameters: Option<ResponseParameters>,
+        }
+
+        let raw = Raw::<T>::deserialize(deserializer)?;
+        let result = match raw.result {
+            Some(result) => result,
+            None => missing_result(raw.ok)?,
+        };
+        Ok(Self {
+            ok: raw.ok,
+            description: raw.description,
+            result,
+            error_code: raw.error_code,
+            parameters: raw.parameters,
+        })
+    }
+}
+
+/// Stand-in for a missing or `null` `result`: empty for types like `Option<T>` and `true` for
+/// booleans of successful requests.
+fn missing_result<'de, T, E>(ok: bool) -> Result<T, E>
+where
+    T: Deserialize<'de>,
+    E: serde::de::Error,
+{
+    if let Ok(result) = T::deserialize(UnitDeserializer::<E>::new()) {
+        return Ok(result);
+    }
+    if !ok {
+        return Err(E::missing_field("result"));
+    }
+    T::deserialize(BoolDeserializer::<E>::new(true))
+        .map_err(|_| E::custom("`ok` is true but `result` is missing or null"))
 }
 
 /// Error on an unsuccessful request.
@@ -55,3 +128,69 @@ pub struct ResponseParameters {
     pub migrate_to_chat_id: Option<i64>,
     pub retry_after: Option<u16>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn into_result_returns_result_when_ok() {
+        let response: MethodResponse<bool> =
+            serde_json::from_str(r#"{"ok":true,"result":true}"#).unwrap();
+        assert_eq!(response.into_result(), Ok(true));
+    }
+
+    #[test]
+    fn into_result_returns_error_details_when_not_ok() {
+        let response: MethodResponse<Option<bool>> = serde_json::from_str(
+            r#"{"ok":false,"description":"Too Many Requests: retry after 5","error_code":429,"parameters":{"retry_after":5}}"#,
+        )
+        .unwrap();
+        let error = response.into_result().unwrap_err();
+        assert_eq!(error.description, "Too Many Requests: retry after 5");
+        assert_eq!(error.error_code, 429);
+        assert_eq!(
+            error.parameters,
+            Some(ResponseParameters {
+                migrate_to_chat_id: None,
+                retry_after: Some(5),
+            })
+        );
+    }
+
+    #[test]
+    fn boolean_result_defaults_to_true_when_missing() {
+        let response: MethodResponse<bool> =
+            serde_json::from_str(r#"{"ok":true,"result":true}"#).unwrap();
+        assert!(response.result);
+
+        for body in [r#"{"ok":true}"#, r#"{"ok":true,"result":null}"#] {
+            let response: MethodResponse<bool> = serde_json::from_str(body).unwrap();
+            assert!(response.result, "{body}");
+        }
+        let response: MethodResponse<MessageOrBool> =
+            serde_json::from_str(r#"{"ok":true}"#).unwrap();
+        assert_eq!(response.result, MessageOrBool::Bool(true));
+    }
+
+    #[test]
+    fn missing_result_of_other_types_is_a_clear_error() {
+        let error =
+            serde_json::from_str::<MethodResponse<Message>>(r#"{"ok":true,"description":"?"}"#)
+                .unwrap_err();
+        assert!(
+            error.to_string().contains("`result` is missing or null"),
+            "{error}"
+        );
+
+        let response: MethodResponse<Option<Message>> =
+            serde_json::from_str(r#"{"ok":true,"result":null}"#).unwrap();
+        assert_eq!(response.result, None);
+
+        let error = serde_json::from_str::<MethodResponse<bool>>(r#"{"ok":false}"#).unwrap_err();
+        assert!(
+            error.to_string().contains("missing field `result`"),
+            "{error}"
+        );
+    }
+}
@@ -1,17 +1,25 @@
+use std::future::{poll_fn, Future};
+use std::ops::ControlFlow;
+use std::pin::pin;
+
+use crate::error::StateError;
 use crate::games::GameHighScore;
-use crate::gifts::{Gifts, OwnedGifts};
+use crate::gifts::{Gifts, OwnedGift, OwnedGifts};
 use crate::inline_mode::{PreparedInlineMessage, SentWebAppMessage};
-use crate::input_file::{HasInputFile, InputFile};
-use crate::input_media::{InputMedia, InputProfilePhoto, InputStoryContent, MediaGroupInputMedia};
-use crate::payments::{StarAmount, StarTransactions};
+use crate::input_file::{Attachments, HasInputFile, InputFile};
+use crate::input_media::{
+    InputMedia, InputPaidMedia, InputProfilePhoto, InputStoryContent, MediaGroupInputMedia,
+};
+use crate::payments::{StarAmount, StarBalanceAlert, StarTransaction, StarTransactions};
 use crate::response::{MessageOrBool, MethodResponse};
 use crate::stickers::{Sticker, StickerSet};
 use crate::types::{
-    BotCommand, BotDescription, BotName, BotShortDescription, BusinessConnection,
-    ChatAdministratorRights, ChatFullInfo, ChatInviteLink, ChatMember, File, ForumTopic,
-    MenuButton, Message, MessageId, Poll, Story, User, UserChatBoosts, UserProfilePhotos,
+    BotCommand, BotCommandScope, BotDescription, BotName, BotShortDescription, BusinessConnection,
+    ChatAdministratorRights, ChatFullInfo, ChatId, ChatInviteLink, ChatMember, File, ForumTopic,
+    MenuButton, Message, MessageId, Poll, PollOption, Story, User, UserChatBoosts,
+    UserProfilePhotos,
 };
-use crate::updates::{Update, WebhookInfo};
+use crate::updates::{HealthStatus, Update, WebhookInfo};
 
 macro_rules! request {
     ($name:ident, $return:ty) => {
@@ -69,6 +77,68 @@ macro_rules! request_f {
     }
 }
 
+/// Poll both futures concurrently until both are done.
+async fn join<A, B>(a: A, b: B) -> (A::Output, B::Output)
+where
+    A: Future,
+    B: Future,
+{
+    use std::task::Poll;
+
+    let mut a = pin!(a);
+    let mut b = pin!(b);
+    let mut a_output = None;
+    let mut b_output = None;
+    poll_fn(|cx| {
+        if a_output.is_none() {
+            if let Poll::Ready(output) = a.as_mut().poll(cx) {
+                a_output = Some(output);
+            }
+        }
+        if b_output.is_none() {
+            if let Poll::Ready(output) = b.as_mut().poll(cx) {
+                b_output = Some(output);
+            }
+        }
+        match (a_output.take(), b_output.take()) {
+            (Some(a), Some(b)) => Poll::Ready((a, b)),
+            (a, b) => {
+                a_output = a;
+                b_output = b;
+                Poll::Pending
+            }
+        }
+    })
+    .await
+}
+
+/// Poll all futures concurrently until all are done, the outputs are in the order of `futures`.
+#[cfg(all(feature = "client-reqwest", not(target_arch = "wasm32")))]
+pub async fn join_all<F: Future>(futures: Vec<F>) -> Vec<F::Output> {
+    use std::task::Poll;
+
+    let mut futures: Vec<_> = futures.into_iter().map(Box::pin).collect();
+    let mut outputs: Vec<Option<F::Output>> = futures.iter().map(|_| None).collect();
+    poll_fn(|cx| {
+        let mut pending = false;
+        for (future, output) in futures.iter_mut().zip(outputs.iter_mut()) {
+            if output.is_none() {
+                match future.as_mut().poll(cx) {
+                    Poll::Ready(ready) => *output = Some(ready),
+                    Poll::Pending => pending = true,
+                }
+            }
+        }
+        if pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    })
+    .await;
+    outputs.into_iter().flatten().collect()
+}
+
 // Wasm target need not be `Send` because it is single-threaded
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
@@ -97,13 +167,11 @@ where
         &self,
         params: &crate::methods::SendMediaGroupParams,
     ) -> Result<MethodResponse<Vec<Message>>, Self::Error> {
-        let mut files = Vec::new();
+        let mut files = Attachments::default();
 
         macro_rules! replace_attach {
             ($base:ident. $property:ident) => {
-                if let Some(file) = $base.$property.replace_attach_dyn(|| files.len()) {
-                    files.push(file);
-                }
+                $base.$property.replace_attach_dedup(&mut files);
             };
         }
 
@@ -128,7 +196,7 @@ where
             }
         }
 
-        self.request_with_possible_form_data("sendMediaGroup", params, files)
+        self.request_with_possible_form_data("sendMediaGroup", params, files.into_files())
             .await
     }
 
@@ -137,7 +205,37 @@ where
     request_f!(sendAnimation, Message, animation, thumbnail);
     request_f!(sendVoice, Message, voice);
     request_f!(sendVideoNote, Message, video_note, thumbnail);
-    request!(sendPaidMedia, Message);
+
+    async fn send_paid_media(
+        &self,
+        params: &crate::methods::SendPaidMediaParams,
+    ) -> Result<MethodResponse<Message>, Self::Error> {
+        let mut files = Attachments::default();
+
+        macro_rules! replace_attach {
+            ($base:ident. $property:ident) => {
+                $base.$property.replace_attach_dedup(&mut files);
+            };
+        }
+
+        let mut params = params.clone();
+        for media in &mut params.media {
+            match media {
+                InputPaidMedia::Photo(photo) => {
+                    replace_attach!(photo.media);
+                }
+                InputPaidMedia::Video(video) => {
+                    replace_attach!(video.media);
+                    replace_attach!(video.cover);
+                    replace_attach!(video.thumbnail);
+                }
+            }
+        }
+
+        self.request_with_possible_form_data("sendPaidMedia", params, files.into_files())
+            .await
+    }
+
     request!(sendLocation, Message);
     request!(editMessageLiveLocation, MessageOrBool);
     request!(stopMessageLiveLocation, MessageOrBool);
@@ -160,12 +258,35 @@ where
     request!(banChatSenderChat, bool);
     request!(unbanChatSenderChat, bool);
     request!(setChatPermissions, bool);
-    request!(exportChatInviteLink, String);
+
+    /// Call the `exportChatInviteLink` method.
+    ///
+    /// Every call generates a new primary invite link and revokes the previous one, so links
+    /// shared earlier stop working.
+    ///
+    /// See <https://core.telegram.org/bots/api#exportchatinvitelink>.
+    async fn export_chat_invite_link(
+        &self,
+        params: &crate::methods::ExportChatInviteLinkParams,
+    ) -> Result<MethodResponse<String>, Self::Error> {
+        self.request("exportChatInviteLink", Some(params)).await
+    }
     request!(createChatInviteLink, ChatInviteLink);
     request!(editChatInviteLink, ChatInviteLink);
     request!(createChatSubscriptionInviteLink, ChatInviteLink);
     request!(editChatSubscriptionInviteLink, ChatInviteLink);
-    request!(revokeChatInviteLink, ChatInviteLink);
+
+    /// Call the `revokeChatInviteLink` method.
+    ///
+    /// Revoking the primary invite link makes Telegram generate a new one.
+    ///
+    /// See <https://core.telegram.org/bots/api#revokechatinvitelink>.
+    async fn revoke_chat_invite_link(
+        &self,
+        params: &crate::methods::RevokeChatInviteLinkParams,
+    ) -> Result<MethodResponse<ChatInviteLink>, Self::Error> {
+        self.request("revokeChatInviteLink", Some(params)).await
+    }
     request!(approveChatJoinRequest, bool);
     request!(declineChatJoinRequest, bool);
 
@@ -189,6 +310,7 @@ where
     request!(getChat, ChatFullInfo);
     request!(getChatAdministrators, Vec<ChatMember>);
     request!(getChatMemberCount, u32);
+
     request!(getChatMember, ChatMember);
     request!(setChatStickerSet, bool);
     request!(deleteChatStickerSet, bool);
@@ -209,6 +331,7 @@ where
     request!(getBusinessConnection, BusinessConnection);
     request!(getMyCommands, Vec<BotCommand>);
     request!(setMyCommands, bool);
+
     request!(deleteMyCommands, bool);
     request!(setMyName, bool);
     request!(getMyName, BotName);
@@ -269,6 +392,7 @@ where
     request!(declineSuggestedPost, bool);
     request!(deleteMessage, bool);
     request!(deleteMessages, bool);
+
     request_f!(sendSticker, Message, sticker);
     request!(getStickerSet, StickerSet);
 
@@ -332,6 +456,7 @@ where
     request!(removeUserVerification, bool);
     request!(removeChatVerification, bool);
     request!(readBusinessMessage, bool);
+
     request!(deleteBusinessMessages, bool);
     request!(setBusinessAccountName, bool);
     request!(setBusinessAccountUsername, bool);
@@ -450,6 +575,13 @@ where
         Params: serde::ser::Serialize + std::fmt::Debug + std::marker::Send,
         Output: serde::de::DeserializeOwned,
     {
+        debug_assert!(
+            files
+                .iter()
+                .enumerate()
+                .all(|(index, (name, _))| files[..index].iter().all(|(other, _)| other != name)),
+            "attach names have to be unique within one request"
+        );
         if files.is_empty() {
             self.request(method_name, Some(params)).await
         } else {
@@ -458,6 +590,17 @@ where
         }
     }
 
+    /// Call any method with untyped JSON parameters and get the whole JSON response back.
+    ///
+    /// Escape hatch for methods which are not supported by this crate yet.
+    async fn request_raw(
+        &self,
+        method: &str,
+        params: Option<serde_json::Value>,
+    ) -> Result<serde_json::Value, Self::Error> {
+        self.request(method, params).await
+    }
+
     async fn request<Params, Output>(
         &self,
         method: &str,
@@ -477,3 +620,539 @@ where
         Params: serde::ser::Serialize + std::fmt::Debug + std::marker::Send,
         Output: serde::de::DeserializeOwned;
 }
+
+/// Convenience methods on top of [`AsyncTelegramApi`], available for every implementation.
+///
+/// The core trait only has the API methods, so implementing it stays simple. Everything here is
+/// built on the public methods of [`AsyncTelegramApi`].
+// The methods generated by `request!` aren't boxed by `async_trait`, their futures are not `Send`.
+// The helpers therefore call `request` directly instead of awaiting them.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait AsyncTelegramApiExt: AsyncTelegramApi {
+    /// Remove the webhook so that updates can be received with
+    /// [`get_updates`](AsyncTelegramApi::get_updates).
+    async fn switch_to_polling(
+        &self,
+        drop_pending_updates: bool,
+    ) -> Result<MethodResponse<bool>, Self::Error> {
+        let params = crate::methods::DeleteWebhookParams::builder()
+            .drop_pending_updates(drop_pending_updates)
+            .build();
+        self.request("deleteWebhook", Some(&params)).await
+    }
+
+    /// Check that no webhook is set before polling with [`get_updates`](AsyncTelegramApi::get_updates).
+    ///
+    /// Telegram answers `getUpdates` with a 409 conflict while a webhook is set. This costs an
+    /// extra `getWebhookInfo` call, so it's meant to be called once before polling starts.
+    /// Returns [`StateError::WebhookActive`] naming the active webhook.
+    async fn ensure_polling_possible(&self) -> Result<(), Self::Error>
+    where
+        Self::Error: From<StateError>,
+    {
+        let no_params: Option<()> = None;
+        let response: MethodResponse<WebhookInfo> =
+            self.request("getWebhookInfo", no_params).await?;
+        let info = response.result;
+        if info.url.is_empty() {
+            return Ok(());
+        }
+        Err(StateError::WebhookActive { url: info.url }.into())
+    }
+
+    /// Replace any existing webhook with the given one and verify it with `getWebhookInfo`.
+    ///
+    /// The old webhook is deleted first, honoring `drop_pending_updates`. Returns
+    /// [`StateError::WebhookMismatch`] when Telegram reports a different webhook URL afterwards.
+    async fn switch_to_webhook(
+        &self,
+        params: &crate::methods::SetWebhookParams,
+    ) -> Result<MethodResponse<WebhookInfo>, Self::Error>
+    where
+        Self::Error: From<StateError>,
+    {
+        let delete_params = crate::methods::DeleteWebhookParams::builder()
+            .maybe_drop_pending_updates(params.drop_pending_updates)
+            .build();
+        let _: MethodResponse<bool> = self.request("deleteWebhook", Some(&delete_params)).await?;
+        let _: MethodResponse<bool> = self.request("setWebhook", Some(params)).await?;
+        let no_params: Option<()> = None;
+        let info: MethodResponse<WebhookInfo> = self.request("getWebhookInfo", no_params).await?;
+        if info.result.url != params.url {
+            return Err(StateError::WebhookMismatch {
+                expected: params.url.clone(),
+                actual: info.result.url,
+            }
+            .into());
+        }
+        Ok(info)
+    }
+
+    /// Check that the token works with `getMe` and report the state of the webhook from
+    /// `getWebhookInfo`, e.g. for readiness probes.
+    ///
+    /// Both requests are sent concurrently.
+    async fn health_check(&self) -> Result<HealthStatus, Self::Error>
+    where
+        Self::Error: Send,
+    {
+        let (me, webhook_info): (
+            Result<MethodResponse<User>, Self::Error>,
+            Result<MethodResponse<WebhookInfo>, Self::Error>,
+        ) = join(
+            self.request("getMe", None::<()>),
+            self.request("getWebhookInfo", None::<()>),
+        )
+        .await;
+        Ok(HealthStatus::new(me?.result, webhook_info?.result))
+    }
+
+    /// Forward any number of messages with as many `forwardMessages` calls as needed.
+    ///
+    /// Telegram accepts at most
+    /// [`ForwardMessagesParams::MAX_MESSAGE_IDS`](crate::methods::ForwardMessagesParams::MAX_MESSAGE_IDS)
+    /// ids per call, in strictly increasing order, so `message_ids` are sorted and deduplicated
+    /// first. Returns the ids of all sent messages and stops at the first failed call.
+    async fn forward_messages_chunked(
+        &self,
+        from_chat_id: ChatId,
+        chat_id: ChatId,
+        message_ids: &[i32],
+    ) -> Result<Vec<MessageId>, Self::Error> {
+        let mut message_ids = message_ids.to_vec();
+        message_ids.sort_unstable();
+        message_ids.dedup();
+        let mut sent = Vec::new();
+        for chunk in message_ids.chunks(crate::methods::ForwardMessagesParams::MAX_MESSAGE_IDS) {
+            let params = crate::methods::ForwardMessagesParams::builder()
+                .chat_id(chat_id.clone())
+                .from_chat_id(from_chat_id.clone())
+                .message_ids(chunk.to_vec())
+                .build();
+            let response: MethodResponse<Vec<MessageId>> =
+                self.request("forwardMessages", Some(&params)).await?;
+            sent.extend(response.result);
+        }
+        Ok(sent)
+    }
+
+    /// Copy any number of messages with as many `copyMessages` calls as needed.
+    ///
+    /// `params.message_ids` is split into batches of
+    /// [`CopyMessagesParams::MAX_MESSAGE_IDS`](crate::methods::CopyMessagesParams::MAX_MESSAGE_IDS),
+    /// all other params such as `remove_caption` are kept for every call. Telegram requires
+    /// strictly increasing ids, so they are sorted and deduplicated first. Returns the ids of all
+    /// sent messages and stops at the first failed call.
+    async fn copy_messages_chunked(
+        &self,
+        params: &crate::methods::CopyMessagesParams,
+    ) -> Result<Vec<MessageId>, Self::Error> {
+        let mut message_ids = params.message_ids.clone();
+        message_ids.sort_unstable();
+        message_ids.dedup();
+        let mut sent = Vec::new();
+        for chunk in message_ids.chunks(crate::methods::CopyMessagesParams::MAX_MESSAGE_IDS) {
+            let chunk_params = crate::methods::CopyMessagesParams {
+                message_ids: chunk.to_vec(),
+                ..params.clone()
+            };
+            let response: MethodResponse<Vec<MessageId>> =
+                self.request("copyMessages", Some(&chunk_params)).await?;
+            sent.extend(response.result);
+        }
+        Ok(sent)
+    }
+
+    /// Remove all reactions of the bot from a message.
+    async fn clear_reactions(
+        &self,
+        chat_id: ChatId,
+        message_id: i32,
+    ) -> Result<MethodResponse<bool>, Self::Error> {
+        let params = crate::methods::SetMessageReactionParams::clear(chat_id, message_id);
+        self.request("setMessageReaction", Some(&params)).await
+    }
+
+    /// Remove the emoji status of a user who allowed the bot to manage it.
+    async fn clear_emoji_status(&self, user_id: u64) -> Result<MethodResponse<bool>, Self::Error> {
+        let params = crate::methods::SetUserEmojiStatusParams::clear(user_id);
+        self.request("setUserEmojiStatus", Some(&params)).await
+    }
+
+    /// Approve the join requests of all `user_ids` one after the other, awaiting `wait` between
+    /// the calls, e.g. `|| tokio::time::sleep(delay)` to stay below the rate limits.
+    ///
+    /// Telegram has no batch method, a failed call doesn't stop the remaining ones. Returns the
+    /// result for each user in the order of `user_ids`.
+    async fn approve_join_requests<W, Fut>(
+        &self,
+        chat_id: ChatId,
+        user_ids: &[u64],
+        mut wait: W,
+    ) -> Vec<(u64, Result<bool, Self::Error>)>
+    where
+        Self::Error: Send,
+        W: FnMut() -> Fut + Send,
+        Fut: Future<Output = ()> + Send,
+    {
+        let mut results = Vec::with_capacity(user_ids.len());
+        for (index, &user_id) in user_ids.iter().enumerate() {
+            if index > 0 {
+                wait().await;
+            }
+            let params = crate::methods::ApproveChatJoinRequestParams::builder()
+                .chat_id(chat_id.clone())
+                .user_id(user_id)
+                .build();
+            let result: Result<MethodResponse<bool>, Self::Error> =
+                self.request("approveChatJoinRequest", Some(&params)).await;
+            let result = result.map(|response| response.result);
+            results.push((user_id, result));
+        }
+        results
+    }
+
+    /// Decline the join requests of all `user_ids` one after the other, awaiting `wait` between
+    /// the calls, e.g. `|| tokio::time::sleep(delay)` to stay below the rate limits.
+    ///
+    /// Telegram has no batch method, a failed call doesn't stop the remaining ones. Returns the
+    /// result for each user in the order of `user_ids`.
+    async fn decline_join_requests<W, Fut>(
+        &self,
+        chat_id: ChatId,
+        user_ids: &[u64],
+        mut wait: W,
+    ) -> Vec<(u64, Result<bool, Self::Error>)>
+    where
+        Self::Error: Send,
+        W: FnMut() -> Fut + Send,
+        Fut: Future<Output = ()> + Send,
+    {
+        let mut results = Vec::with_capacity(user_ids.len());
+        for (index, &user_id) in user_ids.iter().enumerate() {
+            if index > 0 {
+                wait().await;
+            }
+            let params = crate::methods::DeclineChatJoinRequestParams::builder()
+                .chat_id(chat_id.clone())
+                .user_id(user_id)
+                .build();
+            let result: Result<MethodResponse<bool>, Self::Error> =
+                self.request("declineChatJoinRequest", Some(&params)).await;
+            let result = result.map(|response| response.result);
+            results.push((user_id, result));
+        }
+        results
+    }
+
+    /// Fetch the full chat info and the member count of a chat concurrently.
+    async fn chat_snapshot(&self, chat_id: ChatId) -> Result<(ChatFullInfo, u32), Self::Error>
+    where
+        Self::Error: Send,
+    {
+        let chat_params = crate::methods::GetChatParams::builder()
+            .chat_id(chat_id.clone())
+            .build();
+        let count_params = crate::methods::GetChatMemberCountParams::builder()
+            .chat_id(chat_id)
+            .build();
+        let (chat, member_count): (
+            Result<MethodResponse<ChatFullInfo>, Self::Error>,
+            Result<MethodResponse<u32>, Self::Error>,
+        ) = join(
+            self.request("getChat", Some(&chat_params)),
+            self.request("getChatMemberCount", Some(&count_params)),
+        )
+        .await;
+        Ok((chat?.result, member_count?.result))
+    }
+
+    /// Set the bot commands for the given scope.
+    ///
+    /// Shorthand for [`set_my_commands`](AsyncTelegramApi::set_my_commands) without a `language_code`.
+    async fn set_commands_for(
+        &self,
+        scope: BotCommandScope,
+        commands: Vec<BotCommand>,
+    ) -> Result<MethodResponse<bool>, Self::Error> {
+        let params = crate::methods::SetMyCommandsParams::builder()
+            .commands(commands)
+            .scope(scope)
+            .build();
+        self.request("setMyCommands", Some(&params)).await
+    }
+
+    /// Delete the bot commands of the given scope and optionally only for one language.
+    ///
+    /// Counterpart of [`set_commands_for`](Self::set_commands_for).
+    async fn delete_commands_for(
+        &self,
+        scope: BotCommandScope,
+        language_code: Option<&str>,
+    ) -> Result<MethodResponse<bool>, Self::Error> {
+        let params = crate::methods::DeleteMyCommandsParams::builder()
+            .scope(scope)
+            .maybe_language_code(language_code.map(str::to_owned))
+            .build();
+        self.request("deleteMyCommands", Some(&params)).await
+    }
+
+    /// Remove the inline keyboard of a message.
+    ///
+    /// See [`EditMessageReplyMarkupParams::clear`](crate::methods::EditMessageReplyMarkupParams::clear).
+    async fn clear_reply_markup(
+        &self,
+        chat_id: ChatId,
+        message_id: i32,
+    ) -> Result<MethodResponse<MessageOrBool>, Self::Error> {
+        let params = crate::methods::EditMessageReplyMarkupParams::clear(chat_id, message_id);
+        self.request("editMessageReplyMarkup", Some(&params)).await
+    }
+
+    /// Stop a poll and return its winning options and the total number of voters.
+    ///
+    /// See [`Poll::winning_options`].
+    async fn stop_poll_results(
+        &self,
+        chat_id: ChatId,
+        message_id: i32,
+    ) -> Result<(Vec<PollOption>, u32), Self::Error> {
+        let params = crate::methods::StopPollParams::builder()
+            .chat_id(chat_id)
+            .message_id(message_id)
+            .build();
+        let response: MethodResponse<Poll> = self.request("stopPoll", Some(&params)).await?;
+        Ok(response.result.into_results())
+    }
+
+    /// Delete any number of messages with as many `deleteMessages` calls as needed.
+    ///
+    /// Telegram accepts at most
+    /// [`DeleteMessagesParams::MAX_MESSAGE_IDS`](crate::methods::DeleteMessagesParams::MAX_MESSAGE_IDS)
+    /// ids per call. Stops at the first failed call.
+    async fn delete_messages_chunked(
+        &self,
+        chat_id: ChatId,
+        message_ids: &[i32],
+    ) -> Result<Vec<MethodResponse<bool>>, Self::Error> {
+        let mut responses = Vec::new();
+        for chunk in message_ids.chunks(crate::methods::DeleteMessagesParams::MAX_MESSAGE_IDS) {
+            let params = crate::methods::DeleteMessagesParams::builder()
+                .chat_id(chat_id.clone())
+                .message_ids(chunk.to_vec())
+                .build();
+            responses.push(self.request("deleteMessages", Some(&params)).await?);
+        }
+        Ok(responses)
+    }
+
+    /// Mark a message as read on behalf of a business account.
+    ///
+    /// Shorthand for [`read_business_message`](AsyncTelegramApi::read_business_message).
+    async fn mark_read(
+        &self,
+        business_connection_id: &str,
+        chat_id: i64,
+        message_id: i32,
+    ) -> Result<MethodResponse<bool>, Self::Error> {
+        let params = crate::methods::ReadBusinessMessageParams::builder()
+            .business_connection_id(business_connection_id)
+            .chat_id(chat_id)
+            .message_id(message_id)
+            .build();
+        self.request("readBusinessMessage", Some(&params)).await
+    }
+
+    /// Delete any number of business messages with as many `deleteBusinessMessages` calls as needed.
+    ///
+    /// Telegram accepts at most
+    /// [`DeleteBusinessMessagesParams::MAX_MESSAGE_IDS`](crate::methods::DeleteBusinessMessagesParams::MAX_MESSAGE_IDS)
+    /// ids per call. Stops at the first failed call.
+    async fn delete_business_messages_chunked(
+        &self,
+        business_connection_id: &str,
+        message_ids: &[i32],
+    ) -> Result<Vec<MethodResponse<bool>>, Self::Error> {
+        let mut responses = Vec::new();
+        for chunk in
+            message_ids.chunks(crate::methods::DeleteBusinessMessagesParams::MAX_MESSAGE_IDS)
+        {
+            let params = crate::methods::DeleteBusinessMessagesParams::builder()
+                .business_connection_id(business_connection_id)
+                .message_ids(chunk.to_vec())
+                .build();
+            responses.push(
+                self.request("deleteBusinessMessages", Some(&params))
+                    .await?,
+            );
+        }
+        Ok(responses)
+    }
+
+    /// Convert the gifts `owned_gift_ids` of a business account to Telegram Stars one after the
+    /// other.
+    ///
+    /// Telegram has no batch method. With `stop_on_error` the first failed conversion is the last
+    /// result, otherwise the remaining gifts are still converted. Returns the result for each
+    /// converted gift in the order of `owned_gift_ids`. See
+    /// [`OwnedGifts::convertible_gift_ids`](crate::gifts::OwnedGifts::convertible_gift_ids) to
+    /// select the gifts from [`get_business_account_gifts`](AsyncTelegramApi::get_business_account_gifts).
+    async fn convert_gifts_to_stars(
+        &self,
+        business_connection_id: &str,
+        owned_gift_ids: &[String],
+        stop_on_error: bool,
+    ) -> Vec<(String, Result<bool, Self::Error>)>
+    where
+        Self::Error: Send,
+    {
+        let mut results = Vec::with_capacity(owned_gift_ids.len());
+        for owned_gift_id in owned_gift_ids {
+            let params = crate::methods::ConvertGiftToStarsParams::builder()
+                .business_connection_id(business_connection_id)
+                .owner_gift_id(owned_gift_id.clone())
+                .build();
+            let result: Result<MethodResponse<bool>, Self::Error> =
+                self.request("convertGiftToStars", Some(&params)).await;
+            let result = result.map(|response| response.result);
+            let failed = result.is_err();
+            results.push((owned_gift_id.clone(), result));
+            if failed && stop_on_error {
+                break;
+            }
+        }
+        results
+    }
+
+    /// Collect the gifts of a business account from all pages, starting at `params.offset`.
+    ///
+    /// The filters and `limit` of `params` apply to every page, paging follows `next_offset`
+    /// until Telegram returns none, an empty one or the offset of the current page again. The
+    /// pages are collected like in [`star_transactions_between`](Self::star_transactions_between)
+    /// to not depend on a stream library, use
+    /// [`get_business_account_gifts`](AsyncTelegramApi::get_business_account_gifts) to handle one page at a
+    /// time.
+    async fn all_business_account_gifts(
+        &self,
+        params: &crate::methods::GetBusinessAccountGiftsParams,
+    ) -> Result<Vec<OwnedGift>, Self::Error> {
+        let mut params = params.clone();
+        let mut gifts = Vec::new();
+        loop {
+            let response: MethodResponse<OwnedGifts> = self
+                .request("getBusinessAccountGifts", Some(&params))
+                .await?;
+            let page = response.result;
+            let page_is_empty = page.gifts.is_empty();
+            gifts.extend(page.gifts);
+            match page.next_offset {
+                Some(next_offset)
+                    if !next_offset.is_empty()
+                        && !page_is_empty
+                        && params.offset.as_ref() != Some(&next_offset) =>
+                {
+                    params.offset = Some(next_offset);
+                }
+                _ => return Ok(gifts),
+            }
+        }
+    }
+
+    /// Transfer Stars from a business account to the bot after checking the account's balance.
+    ///
+    /// Costs an extra `getBusinessAccountStarBalance` call. Returns
+    /// [`StateError::InsufficientStarBalance`] without calling `transferBusinessAccountStars` when
+    /// `star_count` exceeds the balance.
+    async fn transfer_business_account_stars_checked(
+        &self,
+        params: &crate::methods::TransferBusinessAccountStarsParams,
+    ) -> Result<MethodResponse<bool>, Self::Error>
+    where
+        Self::Error: From<StateError>,
+    {
+        let balance_params = crate::methods::GetBusinessAccountStarBalanceParams::builder()
+            .business_connection_id(params.business_connection_id.clone())
+            .build();
+        let response: MethodResponse<StarAmount> = self
+            .request("getBusinessAccountStarBalance", Some(&balance_params))
+            .await?;
+        let balance = response.result;
+        if i64::from(params.star_count) > i64::from(balance.amount) {
+            return Err(StateError::InsufficientStarBalance {
+                balance: balance.amount,
+                requested: params.star_count,
+            }
+            .into());
+        }
+        self.request("transferBusinessAccountStars", Some(params))
+            .await
+    }
+
+    /// Poll the bot's Star balance and call `on_low` when it drops below `threshold`.
+    ///
+    /// `on_low` is only called when the balance crosses the threshold, see [`StarBalanceAlert`].
+    /// `wait` is awaited between polls, e.g. `|| tokio::time::sleep(interval)`.
+    /// Polling continues until `on_low` returns [`ControlFlow::Break`] or a request fails.
+    async fn watch_star_balance<F, W, Fut>(
+        &self,
+        threshold: u32,
+        mut on_low: F,
+        mut wait: W,
+    ) -> Result<(), Self::Error>
+    where
+        F: FnMut(u32) -> ControlFlow<()> + Send,
+        W: FnMut() -> Fut + Send,
+        Fut: Future<Output = ()> + Send,
+    {
+        let mut alert = StarBalanceAlert::new(threshold);
+        loop {
+            let no_params: Option<()> = None;
+            let response: MethodResponse<u32> = self.request("getMyStarBalance", no_params).await?;
+            let balance = response.result;
+            if alert.check(balance) && on_low(balance).is_break() {
+                return Ok(());
+            }
+            wait().await;
+        }
+    }
+
+    /// Collect the bot's Star transactions with a `date` between `from` and `to` (both inclusive).
+    ///
+    /// Telegram returns the transactions in chronological order, so paging stops as soon as a
+    /// transaction after `to` is seen.
+    async fn star_transactions_between(
+        &self,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<StarTransaction>, Self::Error> {
+        const LIMIT: u32 = 100;
+
+        let mut transactions = Vec::new();
+        let mut offset = 0;
+        loop {
+            let params = crate::methods::GetStarTransactionsParams::builder()
+                .offset(offset)
+                .limit(LIMIT)
+                .build();
+            let response: MethodResponse<StarTransactions> =
+                self.request("getStarTransactions", Some(&params)).await?;
+            let page = response.result.transactions;
+            let page_len = page.len();
+            for transaction in page {
+                if transaction.date > to {
+                    return Ok(transactions);
+                }
+                if transaction.date >= from {
+                    transactions.push(transaction);
+                }
+            }
+            if page_len < LIMIT as usize {
+                return Ok(transactions);
+            }
+            offset += LIMIT;
+        }
+    }
+}
+
+impl<T: AsyncTelegramApi + ?Sized> AsyncTelegramApiExt for T {}
@@ -1,17 +1,24 @@
+use std::ops::ControlFlow;
+use std::time::Duration;
+
+use crate::error::StateError;
 use crate::games::GameHighScore;
-use crate::gifts::{Gifts, OwnedGifts};
+use crate::gifts::{Gifts, OwnedGift, OwnedGifts};
 use crate::inline_mode::{PreparedInlineMessage, SentWebAppMessage};
-use crate::input_file::{HasInputFile, InputFile};
-use crate::input_media::{InputMedia, InputProfilePhoto, InputStoryContent, MediaGroupInputMedia};
-use crate::payments::{StarAmount, StarTransactions};
+use crate::input_file::{Attachments, HasInputFile, InputFile};
+use crate::input_media::{
+    InputMedia, InputPaidMedia, InputProfilePhoto, InputStoryContent, MediaGroupInputMedia,
+};
+use crate::payments::{StarAmount, StarBalanceAlert, StarTransaction, StarTransactions};
 use crate::response::{MessageOrBool, MethodResponse};
 use crate::stickers::{Sticker, StickerSet};
 use crate::types::{
-    BotCommand, BotDescription, BotName, BotShortDescription, BusinessConnection,
-    ChatAdministratorRights, ChatFullInfo, ChatInviteLink, ChatMember, File, ForumTopic,
-    MenuButton, Message, MessageId, Poll, Story, User, UserChatBoosts, UserProfilePhotos,
+    BotCommand, BotCommandScope, BotDescription, BotName, BotShortDescription, BusinessConnection,
+    ChatAdministratorRights, ChatFullInfo, ChatId, ChatInviteLink, ChatMember, File, ForumTopic,
+    MenuButton, Message, MessageId, Poll, PollOption, Story, User, UserChatBoosts,
+    UserProfilePhotos,
 };
-use crate::updates::{Update, WebhookInfo};
+use crate::updates::{HealthStatus, Update, WebhookInfo};
 
 macro_rules! request {
     ($name:ident, $return:ty) => {
@@ -88,13 +95,11 @@ pub trait TelegramApi {
         &self,
         params: &crate::methods::SendMediaGroupParams,
     ) -> Result<MethodResponse<Vec<Message>>, Self::Error> {
-        let mut files = Vec::new();
+        let mut files = Attachments::default();
 
         macro_rules! replace_attach {
             ($base:ident. $property:ident) => {
-                if let Some(file) = $base.$property.replace_attach_dyn(|| files.len()) {
-                    files.push(file);
-                }
+                $base.$property.replace_attach_dedup(&mut files);
             };
         }
 
@@ -119,7 +124,7 @@ pub trait TelegramApi {
             }
         }
 
-        self.request_with_possible_form_data("sendMediaGroup", params, files)
+        self.request_with_possible_form_data("sendMediaGroup", params, files.into_files())
     }
 
     request_f!(sendDocument, Message, document, thumbnail);
@@ -127,7 +132,36 @@ pub trait TelegramApi {
     request_f!(sendAnimation, Message, animation, thumbnail);
     request_f!(sendVoice, Message, voice);
     request_f!(sendVideoNote, Message, video_note, thumbnail);
-    request!(sendPaidMedia, Message);
+
+    fn send_paid_media(
+        &self,
+        params: &crate::methods::SendPaidMediaParams,
+    ) -> Result<MethodResponse<Message>, Self::Error> {
+        let mut files = Attachments::default();
+
+        macro_rules! replace_attach {
+            ($base:ident. $property:ident) => {
+                $base.$property.replace_attach_dedup(&mut files);
+            };
+        }
+
+        let mut params = params.clone();
+        for media in &mut params.media {
+            match media {
+                InputPaidMedia::Photo(photo) => {
+                    replace_attach!(photo.media);
+                }
+                InputPaidMedia::Video(video) => {
+                    replace_attach!(video.media);
+                    replace_attach!(video.cover);
+                    replace_attach!(video.thumbnail);
+                }
+            }
+        }
+
+        self.request_with_possible_form_data("sendPaidMedia", params, files.into_files())
+    }
+
     request!(sendLocation, Message);
     request!(editMessageLiveLocation, MessageOrBool);
     request!(stopMessageLiveLocation, MessageOrBool);
@@ -150,12 +184,35 @@ pub trait TelegramApi {
     request!(banChatSenderChat, bool);
     request!(unbanChatSenderChat, bool);
     request!(setChatPermissions, bool);
-    request!(exportChatInviteLink, String);
+
+    /// Call the `exportChatInviteLink` method.
+    ///
+    /// Every call generates a new primary invite link and revokes the previous one, so links
+    /// shared earlier stop working.
+    ///
+    /// See <https://core.telegram.org/bots/api#exportchatinvitelink>.
+    fn export_chat_invite_link(
+        &self,
+        params: &crate::methods::ExportChatInviteLinkParams,
+    ) -> Result<MethodResponse<String>, Self::Error> {
+        self.request("exportChatInviteLink", Some(params))
+    }
     request!(createChatInviteLink, ChatInviteLink);
     request!(editChatInviteLink, ChatInviteLink);
     request!(createChatSubscriptionInviteLink, ChatInviteLink);
     request!(editChatSubscriptionInviteLink, ChatInviteLink);
-    request!(revokeChatInviteLink, ChatInviteLink);
+
+    /// Call the `revokeChatInviteLink` method.
+    ///
+    /// Revoking the primary invite link makes Telegram generate a new one.
+    ///
+    /// See <https://core.telegram.org/bots/api#revokechatinvitelink>.
+    fn revoke_chat_invite_link(
+        &self,
+        params: &crate::methods::RevokeChatInviteLinkParams,
+    ) -> Result<MethodResponse<ChatInviteLink>, Self::Error> {
+        self.request("revokeChatInviteLink", Some(params))
+    }
     request!(approveChatJoinRequest, bool);
     request!(declineChatJoinRequest, bool);
 
@@ -178,6 +235,7 @@ pub trait TelegramApi {
     request!(getChat, ChatFullInfo);
     request!(getChatAdministrators, Vec<ChatMember>);
     request!(getChatMemberCount, u32);
+
     request!(getChatMember, ChatMember);
     request!(setChatStickerSet, bool);
     request!(deleteChatStickerSet, bool);
@@ -198,6 +256,7 @@ pub trait TelegramApi {
     request!(getBusinessConnection, BusinessConnection);
     request!(getMyCommands, Vec<BotCommand>);
     request!(setMyCommands, bool);
+
     request!(deleteMyCommands, bool);
     request!(setMyName, bool);
     request!(getMyName, BotName);
@@ -257,6 +316,7 @@ pub trait TelegramApi {
     request!(declineSuggestedPost, bool);
     request!(deleteMessage, bool);
     request!(deleteMessages, bool);
+
     request_f!(sendSticker, Message, sticker);
     request!(getStickerSet, StickerSet);
 
@@ -317,6 +377,7 @@ pub trait TelegramApi {
     request!(removeUserVerification, bool);
     request!(removeChatVerification, bool);
     request!(readBusinessMessage, bool);
+
     request!(deleteBusinessMessages, bool);
     request!(setBusinessAccountName, bool);
     request!(setBusinessAccountUsername, bool);
@@ -433,6 +494,13 @@ pub trait TelegramApi {
         Params: serde::ser::Serialize + std::fmt::Debug,
         Output: serde::de::DeserializeOwned,
     {
+        debug_assert!(
+            files
+                .iter()
+                .enumerate()
+                .all(|(index, (name, _))| files[..index].iter().all(|(other, _)| other != name)),
+            "attach names have to be unique within one request"
+        );
         if files.is_empty() {
             self.request(method_name, Some(params))
         } else {
@@ -440,6 +508,17 @@ pub trait TelegramApi {
         }
     }
 
+    /// Call any method with untyped JSON parameters and get the whole JSON response back.
+    ///
+    /// Escape hatch for methods which are not supported by this crate yet.
+    fn request_raw(
+        &self,
+        method: &str,
+        params: Option<serde_json::Value>,
+    ) -> Result<serde_json::Value, Self::Error> {
+        self.request(method, params)
+    }
+
     fn request<Params, Output>(
         &self,
         method: &str,
@@ -459,3 +538,656 @@ pub trait TelegramApi {
         Params: serde::ser::Serialize + std::fmt::Debug,
         Output: serde::de::DeserializeOwned;
 }
+
+/// Convenience methods on top of [`TelegramApi`], available for every implementation.
+///
+/// The core trait only has the API methods, so implementing it stays simple. Everything here is
+/// built on the public methods of [`TelegramApi`].
+pub trait TelegramApiExt: TelegramApi {
+    /// Remove the webhook so that updates can be received with
+    /// [`get_updates`](TelegramApi::get_updates).
+    fn switch_to_polling(
+        &self,
+        drop_pending_updates: bool,
+    ) -> Result<MethodResponse<bool>, Self::Error> {
+        let params = crate::methods::DeleteWebhookParams::builder()
+            .drop_pending_updates(drop_pending_updates)
+            .build();
+        self.delete_webhook(&params)
+    }
+
+    /// Check that no webhook is set before polling with [`get_updates`](TelegramApi::get_updates).
+    ///
+    /// Telegram answers `getUpdates` with a 409 conflict while a webhook is set. This costs an
+    /// extra `getWebhookInfo` call, so it's meant to be called once before polling starts.
+    /// Returns [`StateError::WebhookActive`] naming the active webhook.
+    fn ensure_polling_possible(&self) -> Result<(), Self::Error>
+    where
+        Self::Error: From<StateError>,
+    {
+        let info = self.get_webhook_info()?.result;
+        if info.url.is_empty() {
+            return Ok(());
+        }
+        Err(StateError::WebhookActive { url: info.url }.into())
+    }
+
+    /// Replace any existing webhook with the given one and verify it with `getWebhookInfo`.
+    ///
+    /// The old webhook is deleted first, honoring `drop_pending_updates`. Returns
+    /// [`StateError::WebhookMismatch`] when Telegram reports a different webhook URL afterwards.
+    fn switch_to_webhook(
+        &self,
+        params: &crate::methods::SetWebhookParams,
+    ) -> Result<MethodResponse<WebhookInfo>, Self::Error>
+    where
+        Self::Error: From<StateError>,
+    {
+        let delete_params = crate::methods::DeleteWebhookParams::builder()
+            .maybe_drop_pending_updates(params.drop_pending_updates)
+            .build();
+        self.delete_webhook(&delete_params)?;
+        self.set_webhook(params)?;
+        let info = self.get_webhook_info()?;
+        if info.result.url != params.url {
+            return Err(StateError::WebhookMismatch {
+                expected: params.url.clone(),
+                actual: info.result.url,
+            }
+            .into());
+        }
+        Ok(info)
+    }
+
+    /// Check that the token works with `getMe` and report the state of the webhook from
+    /// `getWebhookInfo`, e.g. for readiness probes.
+    fn health_check(&self) -> Result<HealthStatus, Self::Error> {
+        let me = self.get_me()?.result;
+        let webhook_info = self.get_webhook_info()?.result;
+        Ok(HealthStatus::new(me, webhook_info))
+    }
+
+    /// Forward any number of messages with as many `forwardMessages` calls as needed.
+    ///
+    /// Telegram accepts at most
+    /// [`ForwardMessagesParams::MAX_MESSAGE_IDS`](crate::methods::ForwardMessagesParams::MAX_MESSAGE_IDS)
+    /// ids per call, in strictly increasing order, so `message_ids` are sorted and deduplicated
+    /// first. Returns the ids of all sent messages and stops at the first failed call.
+    fn forward_messages_chunked(
+        &self,
+        from_chat_id: ChatId,
+        chat_id: ChatId,
+        message_ids: &[i32],
+    ) -> Result<Vec<MessageId>, Self::Error> {
+        let mut message_ids = message_ids.to_vec();
+        message_ids.sort_unstable();
+        message_ids.dedup();
+        let mut sent = Vec::new();
+        for chunk in message_ids.chunks(crate::methods::ForwardMessagesParams::MAX_MESSAGE_IDS) {
+            let params = crate::methods::ForwardMessagesParams::builder()
+                .chat_id(chat_id.clone())
+                .from_chat_id(from_chat_id.clone())
+                .message_ids(chunk.to_vec())
+                .build();
+            sent.extend(self.forward_messages(&params)?.result);
+        }
+        Ok(sent)
+    }
+
+    /// Copy any number of messages with as many `copyMessages` calls as needed.
+    ///
+    /// `params.message_ids` is split into batches of
+    /// [`CopyMessagesParams::MAX_MESSAGE_IDS`](crate::methods::CopyMessagesParams::MAX_MESSAGE_IDS),
+    /// all other params such as `remove_caption` are kept for every call. Telegram requires
+    /// strictly increasing ids, so they are sorted and deduplicated first. Returns the ids of all
+    /// sent messages and stops at the first failed call.
+    fn copy_messages_chunked(
+        &self,
+        params: &crate::methods::CopyMessagesParams,
+    ) -> Result<Vec<MessageId>, Self::Error> {
+        let mut message_ids = params.message_ids.clone();
+        message_ids.sort_unstable();
+        message_ids.dedup();
+        let mut sent = Vec::new();
+        for chunk in message_ids.chunks(crate::methods::CopyMessagesParams::MAX_MESSAGE_IDS) {
+            let chunk_params = crate::methods::CopyMessagesParams {
+                message_ids: chunk.to_vec(),
+                ..params.clone()
+            };
+            sent.extend(self.copy_messages(&chunk_params)?.result);
+        }
+        Ok(sent)
+    }
+
+    /// Remove all reactions of the bot from a message.
+    fn clear_reactions(
+        &self,
+        chat_id: ChatId,
+        message_id: i32,
+    ) -> Result<MethodResponse<bool>, Self::Error> {
+        let params = crate::methods::SetMessageReactionParams::clear(chat_id, message_id);
+        self.set_message_reaction(&params)
+    }
+
+    /// Remove the emoji status of a user who allowed the bot to manage it.
+    fn clear_emoji_status(&self, user_id: u64) -> Result<MethodResponse<bool>, Self::Error> {
+        let params = crate::methods::SetUserEmojiStatusParams::clear(user_id);
+        self.set_user_emoji_status(&params)
+    }
+
+    /// Approve the join requests of all `user_ids` one after the other, sleeping `delay` between
+    /// the calls to stay below the rate limits.
+    ///
+    /// Telegram has no batch method, a failed call doesn't stop the remaining ones. Returns the
+    /// result for each user in the order of `user_ids`.
+    fn approve_join_requests(
+        &self,
+        chat_id: ChatId,
+        user_ids: &[u64],
+        delay: Duration,
+    ) -> Vec<(u64, Result<bool, Self::Error>)> {
+        let mut results = Vec::with_capacity(user_ids.len());
+        for (index, &user_id) in user_ids.iter().enumerate() {
+            if index > 0 {
+                std::thread::sleep(delay);
+            }
+            let params = crate::methods::ApproveChatJoinRequestParams::builder()
+                .chat_id(chat_id.clone())
+                .user_id(user_id)
+                .build();
+            let result = self
+                .approve_chat_join_request(&params)
+                .map(|response| response.result);
+            results.push((user_id, result));
+        }
+        results
+    }
+
+    /// Decline the join requests of all `user_ids` one after the other, sleeping `delay` between
+    /// the calls to stay below the rate limits.
+    ///
+    /// Telegram has no batch method, a failed call doesn't stop the remaining ones. Returns the
+    /// result for each user in the order of `user_ids`.
+    fn decline_join_requests(
+        &self,
+        chat_id: ChatId,
+        user_ids: &[u64],
+        delay: Duration,
+    ) -> Vec<(u64, Result<bool, Self::Error>)> {
+        let mut results = Vec::with_capacity(user_ids.len());
+        for (index, &user_id) in user_ids.iter().enumerate() {
+            if index > 0 {
+                std::thread::sleep(delay);
+            }
+            let params = crate::methods::DeclineChatJoinRequestParams::builder()
+                .chat_id(chat_id.clone())
+                .user_id(user_id)
+                .build();
+            let result = self
+                .decline_chat_join_request(&params)
+                .map(|response| response.result);
+            results.push((user_id, result));
+        }
+        results
+    }
+
+    /// Fetch the full chat info and the member count of a chat one after the other.
+    fn chat_snapshot(&self, chat_id: ChatId) -> Result<(ChatFullInfo, u32), Self::Error> {
+        let chat_params = crate::methods::GetChatParams::builder()
+            .chat_id(chat_id.clone())
+            .build();
+        let count_params = crate::methods::GetChatMemberCountParams::builder()
+            .chat_id(chat_id)
+            .build();
+        let chat = self.get_chat(&chat_params)?.result;
+        let member_count = self.get_chat_member_count(&count_params)?.result;
+        Ok((chat, member_count))
+    }
+
+    /// Set the bot commands for the given scope.
+    ///
+    /// Shorthand for [`set_my_commands`](TelegramApi::set_my_commands) without a `language_code`.
+    fn set_commands_for(
+        &self,
+        scope: BotCommandScope,
+        commands: Vec<BotCommand>,
+    ) -> Result<MethodResponse<bool>, Self::Error> {
+        let params = crate::methods::SetMyCommandsParams::builder()
+            .commands(commands)
+            .scope(scope)
+            .build();
+        self.set_my_commands(&params)
+    }
+
+    /// Delete the bot commands of the given scope and optionally only for one language.
+    ///
+    /// Counterpart of [`set_commands_for`](Self::set_commands_for).
+    fn delete_commands_for(
+        &self,
+        scope: BotCommandScope,
+        language_code: Option<&str>,
+    ) -> Result<MethodResponse<bool>, Self::Error> {
+        let params = crate::methods::DeleteMyCommandsParams::builder()
+            .scope(scope)
+            .maybe_language_code(language_code.map(str::to_owned))
+            .build();
+        self.delete_my_commands(&params)
+    }
+
+    /// Remove the inline keyboard of a message.
+    ///
+    /// See [`EditMessageReplyMarkupParams::clear`](crate::methods::EditMessageReplyMarkupParams::clear).
+    fn clear_reply_markup(
+        &self,
+        chat_id: ChatId,
+        message_id: i32,
+    ) -> Result<MethodResponse<MessageOrBool>, Self::Error> {
+        let params = crate::methods::EditMessageReplyMarkupParams::clear(chat_id, message_id);
+        self.edit_message_reply_markup(&params)
+    }
+
+    /// Stop a poll and return its winning options and the total number of voters.
+    ///
+    /// See [`Poll::winning_options`].
+    fn stop_poll_results(
+        &self,
+        chat_id: ChatId,
+        message_id: i32,
+    ) -> Result<(Vec<PollOption>, u32), Self::Error> {
+        let params = crate::methods::StopPollParams::builder()
+            .chat_id(chat_id)
+            .message_id(message_id)
+            .build();
+        Ok(self.stop_poll(&params)?.result.into_results())
+    }
+
+    /// Delete any number of messages with as many `deleteMessages` calls as needed.
+    ///
+    /// Telegram accepts at most
+    /// [`DeleteMessagesParams::MAX_MESSAGE_IDS`](crate::methods::DeleteMessagesParams::MAX_MESSAGE_IDS)
+    /// ids per call. Stops at the first failed call.
+    fn delete_messages_chunked(
+        &self,
+        chat_id: ChatId,
+        message_ids: &[i32],
+    ) -> Result<Vec<MethodResponse<bool>>, Self::Error> {
+        let mut responses = Vec::new();
+        for chunk in message_ids.chunks(crate::methods::DeleteMessagesParams::MAX_MESSAGE_IDS) {
+            let params = crate::methods::DeleteMessagesParams::builder()
+                .chat_id(chat_id.clone())
+                .message_ids(chunk.to_vec())
+                .build();
+            responses.push(self.delete_messages(&params)?);
+        }
+        Ok(responses)
+    }
+
+    /// Mark a message as read on behalf of a business account.
+    ///
+    /// Shorthand for [`read_business_message`](TelegramApi::read_business_message).
+    fn mark_read(
+        &self,
+        business_connection_id: &str,
+        chat_id: i64,
+        message_id: i32,
+    ) -> Result<MethodResponse<bool>, Self::Error> {
+        let params = crate::methods::ReadBusinessMessageParams::builder()
+            .business_connection_id(business_connection_id)
+            .chat_id(chat_id)
+            .message_id(message_id)
+            .build();
+        self.read_business_message(&params)
+    }
+
+    /// Delete any number of business messages with as many `deleteBusinessMessages` calls as needed.
+    ///
+    /// Telegram accepts at most
+    /// [`DeleteBusinessMessagesParams::MAX_MESSAGE_IDS`](crate::methods::DeleteBusinessMessagesParams::MAX_MESSAGE_IDS)
+    /// ids per call. Stops at the first failed call.
+    fn delete_business_messages_chunked(
+        &self,
+        business_connection_id: &str,
+        message_ids: &[i32],
+    ) -> Result<Vec<MethodResponse<bool>>, Self::Error> {
+        let mut responses = Vec::new();
+        for chunk in
+            message_ids.chunks(crate::methods::DeleteBusinessMessagesParams::MAX_MESSAGE_IDS)
+        {
+            let params = crate::methods::DeleteBusinessMessagesParams::builder()
+                .business_connection_id(business_connection_id)
+                .message_ids(chunk.to_vec())
+                .build();
+            responses.push(self.delete_business_messages(&params)?);
+        }
+        Ok(responses)
+    }
+
+    /// Convert the gifts `owned_gift_ids` of a business account to Telegram Stars one after the
+    /// other.
+    ///
+    /// Telegram has no batch method. With `stop_on_error` the first failed conversion is the last
+    /// result, otherwise the remaining gifts are still converted. Returns the result for each
+    /// converted gift in the order of `owned_gift_ids`. See
+    /// [`OwnedGifts::convertible_gift_ids`](crate::gifts::OwnedGifts::convertible_gift_ids) to
+    /// select the gifts from [`get_business_account_gifts`](TelegramApi::get_business_account_gifts).
+    fn convert_gifts_to_stars(
+        &self,
+        business_connection_id: &str,
+        owned_gift_ids: &[String],
+        stop_on_error: bool,
+    ) -> Vec<(String, Result<bool, Self::Error>)> {
+        let mut results = Vec::with_capacity(owned_gift_ids.len());
+        for owned_gift_id in owned_gift_ids {
+            let params = crate::methods::ConvertGiftToStarsParams::builder()
+                .business_connection_id(business_connection_id)
+                .owner_gift_id(owned_gift_id.clone())
+                .build();
+            let result = self
+                .convert_gift_to_stars(&params)
+                .map(|response| response.result);
+            let failed = result.is_err();
+            results.push((owned_gift_id.clone(), result));
+            if failed && stop_on_error {
+                break;
+            }
+        }
+        results
+    }
+
+    /// Collect the gifts of a business account from all pages, starting at `params.offset`.
+    ///
+    /// The filters and `limit` of `params` apply to every page, paging follows `next_offset`
+    /// until Telegram returns none, an empty one or the offset of the current page again. The
+    /// pages are collected like in [`star_transactions_between`](Self::star_transactions_between)
+    /// to not depend on a stream library, use
+    /// [`get_business_account_gifts`](TelegramApi::get_business_account_gifts) to handle one page at a
+    /// time.
+    fn all_business_account_gifts(
+        &self,
+        params: &crate::methods::GetBusinessAccountGiftsParams,
+    ) -> Result<Vec<OwnedGift>, Self::Error> {
+        let mut params = params.clone();
+        let mut gifts = Vec::new();
+        loop {
+            let page = self.get_business_account_gifts(&params)?.result;
+            let page_is_empty = page.gifts.is_empty();
+            gifts.extend(page.gifts);
+            match page.next_offset {
+                Some(next_offset)
+                    if !next_offset.is_empty()
+                        && !page_is_empty
+                        && params.offset.as_ref() != Some(&next_offset) =>
+                {
+                    params.offset = Some(next_offset);
+                }
+                _ => return Ok(gifts),
+            }
+        }
+    }
+
+    /// Transfer Stars from a business account to the bot after checking the account's balance.
+    ///
+    /// Costs an extra `getBusinessAccountStarBalance` call. Returns
+    /// [`StateError::InsufficientStarBalance`] without calling `transferBusinessAccountStars` when
+    /// `star_count` exceeds the balance.
+    fn transfer_business_account_stars_checked(
+        &self,
+        params: &crate::methods::TransferBusinessAccountStarsParams,
+    ) -> Result<MethodResponse<bool>, Self::Error>
+    where
+        Self::Error: From<StateError>,
+    {
+        let balance_params = crate::methods::GetBusinessAccountStarBalanceParams::builder()
+            .business_connection_id(params.business_connection_id.clone())
+            .build();
+        let balance = self
+            .get_business_account_star_balance(&balance_params)?
+            .result;
+        if i64::from(params.star_count) > i64::from(balance.amount) {
+            return Err(StateError::InsufficientStarBalance {
+                balance: balance.amount,
+                requested: params.star_count,
+            }
+            .into());
+        }
+        self.transfer_business_account_stars(params)
+    }
+
+    /// Poll the bot's Star balance every `interval` and call `on_low` when it drops below `threshold`.
+    ///
+    /// `on_low` is only called when the balance crosses the threshold, see [`StarBalanceAlert`].
+    /// Polling continues until `on_low` returns [`ControlFlow::Break`] or a request fails.
+    fn watch_star_balance<F>(
+        &self,
+        threshold: u32,
+        interval: Duration,
+        mut on_low: F,
+    ) -> Result<(), Self::Error>
+    where
+        F: FnMut(u32) -> ControlFlow<()>,
+    {
+        let mut alert = StarBalanceAlert::new(threshold);
+        loop {
+            let balance = self.get_my_star_balance()?.result;
+            if alert.check(balance) && on_low(balance).is_break() {
+                return Ok(());
+            }
+            std::thread::sleep(interval);
+        }
+    }
+
+    /// Collect the bot's Star transactions with a `date` between `from` and `to` (both inclusive).
+    ///
+    /// Telegram returns the transactions in chronological order, so paging stops as soon as a
+    /// transaction after `to` is seen.
+    fn star_transactions_between(
+        &self,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<StarTransaction>, Self::Error> {
+        const LIMIT: u32 = 100;
+
+        let mut transactions = Vec::new();
+        let mut offset = 0;
+        loop {
+            let params = crate::methods::GetStarTransactionsParams::builder()
+                .offset(offset)
+                .limit(LIMIT)
+                .build();
+            let page = self.get_star_transactions(&params)?.result.transactions;
+            let page_len = page.len();
+            for transaction in page {
+                if transaction.date > to {
+                    return Ok(transactions);
+                }
+                if transaction.date >= from {
+                    transactions.push(transaction);
+                }
+            }
+            if page_len < LIMIT as usize {
+                return Ok(transactions);
+            }
+            offset += LIMIT;
+        }
+    }
+}
+
+impl<T: TelegramApi + ?Sized> TelegramApiExt for T {}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+
+    use super::*;
+
+    /// Answers the queued `(method, result)` pairs in order and fails once they run out.
+    struct FakeApi {
+        responses: RefCell<VecDeque<(&'static str, Result<serde_json::Value, String>)>>,
+    }
+
+    impl FakeApi {
+        fn new<I: IntoIterator<Item = (&'static str, serde_json::Value)>>(responses: I) -> Self {
+            Self {
+                responses: RefCell::new(
+                    responses
+                        .into_iter()
+                        .map(|(method, result)| (method, Ok(result)))
+                        .collect(),
+                ),
+            }
+        }
+
+        /// Let the next call of `method` after the queued responses fail with `error`.
+        fn with_error(self, method: &'static str, error: &str) -> Self {
+            self.responses
+                .borrow_mut()
+                .push_back((method, Err(error.to_string())));
+            self
+        }
+    }
+
+    impl TelegramApi for FakeApi {
+        type Error = String;
+
+        fn request<Params, Output>(
+            &self,
+            method: &str,
+            _params: Option<Params>,
+        ) -> Result<Output, Self::Error>
+        where
+            Params: serde::ser::Serialize + std::fmt::Debug,
+            Output: serde::de::DeserializeOwned,
+        {
+            let (expected_method, result) = self
+                .responses
+                .borrow_mut()
+                .pop_front()
+                .ok_or("no more responses")?;
+            assert_eq!(method, expected_method);
+            serde_json::from_value(serde_json::json!({"ok": true, "result": result?}))
+                .map_err(|error| error.to_string())
+        }
+
+        fn request_with_form_data<Params, Output>(
+            &self,
+            _method: &str,
+            _params: Params,
+            _files: Vec<(String, InputFile)>,
+        ) -> Result<Output, Self::Error>
+        where
+            Params: serde::ser::Serialize + std::fmt::Debug,
+            Output: serde::de::DeserializeOwned,
+        {
+            Err("FakeApi doesn't support form data".to_string())
+        }
+    }
+
+    #[test]
+    fn watch_star_balance_alerts_once_on_crossing() {
+        let api = FakeApi::new(
+            [500, 300, 150, 90, 60, 30]
+                .map(|balance| ("getMyStarBalance", serde_json::Value::from(balance))),
+        );
+        let mut alerts = Vec::new();
+        let result = api.watch_star_balance(100, Duration::ZERO, |balance| {
+            alerts.push(balance);
+            ControlFlow::Continue(())
+        });
+        assert_eq!(result, Err("no more responses".to_string()));
+        assert_eq!(alerts, [90]);
+    }
+
+    #[test]
+    fn stop_poll_results_returns_winners() {
+        let poll = serde_json::json!({
+            "id": "poll",
+            "question": "Lunch?",
+            "options": [
+                {"text": "Pizza", "voter_count": 3},
+                {"text": "Sushi", "voter_count": 1},
+                {"text": "Pasta", "voter_count": 3}
+            ],
+            "total_voter_count": 7,
+            "is_closed": true,
+            "is_anonymous": true,
+            "type": "regular",
+            "allows_multiple_answers": false
+        });
+        let api = FakeApi::new([("stopPoll", poll)]);
+
+        let (winners, total_voter_count) = api.stop_poll_results(ChatId::Integer(1), 2).unwrap();
+        let winners: Vec<&str> = winners.iter().map(|option| option.text.as_str()).collect();
+        assert_eq!(winners, ["Pizza", "Pasta"]);
+        assert_eq!(total_voter_count, 7);
+    }
+
+    #[test]
+    fn health_check_reports_bot_and_webhook() {
+        let api = FakeApi::new([
+            (
+                "getMe",
+                serde_json::json!({"id": 1, "is_bot": true, "first_name": "Bot", "username": "probe_bot"}),
+            ),
+            (
+                "getWebhookInfo",
+                serde_json::json!({
+                    "url": "https://example.com/hook",
+                    "has_custom_certificate": false,
+                    "pending_update_count": 3,
+                    "last_error_date": 1_700_000_000,
+                    "last_error_message": "Connection refused"
+                }),
+            ),
+        ]);
+
+        let status = api.health_check().unwrap();
+        assert_eq!(
+            status,
+            HealthStatus {
+                username: Some("probe_bot".to_string()),
+                webhook_url: Some("https://example.com/hook".to_string()),
+                pending_update_count: 3,
+                last_error_date: Some(1_700_000_000),
+                last_error_message: Some("Connection refused".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn health_check_without_webhook() {
+        let api = FakeApi::new([
+            (
+                "getMe",
+                serde_json::json!({"id": 1, "is_bot": true, "first_name": "Bot"}),
+            ),
+            (
+                "getWebhookInfo",
+                serde_json::json!({"url": "", "has_custom_certificate": false, "pending_update_count": 0}),
+            ),
+        ]);
+
+        let status = api.health_check().unwrap();
+        assert_eq!(status.webhook_url, None);
+        assert_eq!(status.last_error_message, None);
+    }
+
+    #[test]
+    fn health_check_fails_with_invalid_token() {
+        let api = FakeApi::new([]).with_error("getMe", "Unauthorized");
+        assert_eq!(api.health_check(), Err("Unauthorized".to_string()));
+    }
+
+    #[test]
+    fn custom_implementations_get_the_extension_methods() {
+        fn assert_extended<T: TelegramApiExt + ?Sized>(_: &T) {}
+
+        let api = FakeApi::new([
+            ("deleteWebhook", serde_json::Value::Bool(true)),
+            ("setMessageReaction", serde_json::Value::Bool(true)),
+        ]);
+        assert_extended(&api);
+        assert!(api.switch_to_polling(true).unwrap().result);
+        assert!(api.clear_reactions(ChatId::Integer(1), 2).unwrap().result);
+    }
+}
@@ -0,0 +1,89 @@
+//! Caches shared between clones of the clients.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub struct TtlCache<T> {
+    ttl: Duration,
+    entry: Mutex<Option<(Instant, T)>>,
+}
+
+impl<T: Clone> TtlCache<T> {
+    pub const fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entry: Mutex::new(None),
+        }
+    }
+
+    /// Returns a clone of the cached value when it is not older than the TTL.
+    pub fn get(&self) -> Option<T> {
+        let entry = self
+            .entry
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        entry
+            .as_ref()
+            .filter(|(stored_at, _)| stored_at.elapsed() <= self.ttl)
+            .map(|(_, value)| value.clone())
+    }
+
+    pub fn set(&self, value: T) {
+        let mut entry = self
+            .entry
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        *entry = Some((Instant::now(), value));
+    }
+
+    pub fn clear(&self) {
+        let mut entry = self
+            .entry
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        *entry = None;
+    }
+}
+
+/// Forum topic icon stickers rarely change, so they are kept for a day.
+pub const FORUM_TOPIC_ICON_STICKERS_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Values per key without an expiry time.
+#[derive(Debug)]
+pub struct KeyedCache<K, V> {
+    entries: Mutex<HashMap<K, V>>,
+}
+
+impl<K: Eq + Hash, V: Clone + PartialEq> KeyedCache<K, V> {
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.lock().get(key).cloned()
+    }
+
+    pub fn insert(&self, key: K, value: V) {
+        self.lock().insert(key, value);
+    }
+
+    pub fn remove(&self, key: &K) {
+        self.lock().remove(key);
+    }
+
+    /// Removes every key cached with `value`.
+    pub fn remove_value(&self, value: &V) {
+        self.lock().retain(|_, cached| cached != value);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<K, V>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
@@ -1,7 +1,10 @@
 //! [Available Types](https://core.telegram.org/bots/api#available-types) of the Bot API.
 
+use std::ops::Range;
+
 use serde::{Deserialize, Serialize};
 
+use crate::error::ValidationError;
 use crate::games::{CallbackGame, Game};
 use crate::gifts::{AcceptedGiftTypes, GiftInfo, UniqueGiftInfo};
 use crate::macros::{apistruct, apply};
@@ -10,7 +13,7 @@ use crate::passport::PassportData;
 use crate::payments::{Invoice, RefundedPayment, StarAmount, SuccessfulPayment};
 use crate::stickers::Sticker;
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(untagged)]
 pub enum ChatId {
     Integer(i64),
@@ -54,9 +57,10 @@ pub enum ChatAction {
     UploadVideoNote,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum BotCommandScope {
+    #[default]
     Default,
     AllPrivateChats,
     AllGroupChats,
@@ -66,6 +70,51 @@ pub enum BotCommandScope {
     ChatMember(BotCommandScopeChatMember),
 }
 
+impl BotCommandScope {
+    /// Commands shown in all private chats with the bot.
+    #[must_use]
+    pub const fn all_private_chats() -> Self {
+        Self::AllPrivateChats
+    }
+
+    /// Commands shown in all group and supergroup chats.
+    #[must_use]
+    pub const fn all_group_chats() -> Self {
+        Self::AllGroupChats
+    }
+
+    /// Commands shown to all group and supergroup administrators.
+    #[must_use]
+    pub const fn all_chat_administrators() -> Self {
+        Self::AllChatAdministrators
+    }
+
+    /// Commands shown in one specific chat.
+    #[must_use]
+    pub fn chat<C: Into<ChatId>>(chat_id: C) -> Self {
+        Self::Chat(BotCommandScopeChat {
+            chat_id: chat_id.into(),
+        })
+    }
+
+    /// Commands shown to all administrators of one specific group or supergroup chat.
+    #[must_use]
+    pub fn chat_administrators<C: Into<ChatId>>(chat_id: C) -> Self {
+        Self::ChatAdministrators(BotCommandScopeChatAdministrators {
+            chat_id: chat_id.into(),
+        })
+    }
+
+    /// Commands shown to one specific member of a group or supergroup chat.
+    #[must_use]
+    pub fn chat_member<C: Into<ChatId>>(chat_id: C, user_id: u64) -> Self {
+        Self::ChatMember(BotCommandScopeChatMember {
+            chat_id: chat_id.into(),
+            user_id,
+        })
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct BotCommandScopeChat {
@@ -109,6 +158,59 @@ pub enum ChatMember {
     Kicked(ChatMemberBanned),
 }
 
+impl ChatMember {
+    #[must_use]
+    pub const fn user(&self) -> &User {
+        match self {
+            Self::Creator(member) => &member.user,
+            Self::Administrator(member) => &member.user,
+            Self::Member(member) => &member.user,
+            Self::Restricted(member) => &member.user,
+            Self::Left(member) => &member.user,
+            Self::Kicked(member) => &member.user,
+        }
+    }
+
+    #[must_use]
+    pub const fn is_owner(&self) -> bool {
+        matches!(self, Self::Creator(_))
+    }
+
+    /// Whether the member is an administrator or the owner of the chat.
+    #[must_use]
+    pub const fn is_admin(&self) -> bool {
+        matches!(self, Self::Creator(_) | Self::Administrator(_))
+    }
+}
+
+/// Helpers for the result of `getChatAdministrators`.
+pub trait ChatAdministrators {
+    /// The owner of the chat, unless they are hidden from the administrator list.
+    fn owner(&self) -> Option<&ChatMember>;
+
+    fn admins_excluding_owner(&self) -> Vec<&ChatMember>;
+
+    /// Whether the user is an administrator or the owner of the chat.
+    fn has_admin(&self, user_id: u64) -> bool;
+}
+
+impl ChatAdministrators for [ChatMember] {
+    fn owner(&self) -> Option<&ChatMember> {
+        self.iter().find(|member| member.is_owner())
+    }
+
+    fn admins_excluding_owner(&self) -> Vec<&ChatMember> {
+        self.iter()
+            .filter(|member| matches!(member, ChatMember::Administrator(_)))
+            .collect()
+    }
+
+    fn has_admin(&self, user_id: u64) -> bool {
+        self.iter()
+            .any(|member| member.is_admin() && member.user().id == user_id)
+    }
+}
+
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum ChatType {
@@ -159,6 +261,23 @@ pub enum MenuButton {
     Default,
 }
 
+impl MenuButton {
+    /// Menu button opening the list of the bot's commands.
+    #[must_use]
+    pub const fn commands() -> Self {
+        Self::Commands
+    }
+
+    /// Menu button launching the Mini App at `url`.
+    #[must_use]
+    pub fn web_app<T: Into<String>, U: Into<String>>(text: T, url: U) -> Self {
+        Self::WebApp(MenuButtonWebApp {
+            text: text.into(),
+            web_app: WebAppInfo { url: url.into() },
+        })
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ChatBackground {
@@ -335,6 +454,57 @@ pub struct User {
     pub has_main_web_app: Option<bool>,
 }
 
+impl User {
+    /// Inline mention of the user with [`ParseMode::MarkdownV2`] showing the escaped name.
+    #[must_use]
+    pub fn mention_markdown(&self) -> String {
+        format!(
+            "[{}](tg://user?id={})",
+            ParseMode::MarkdownV2.escape(&self.full_name()),
+            self.id
+        )
+    }
+
+    /// Inline mention of the user with [`ParseMode::Html`] showing the escaped name.
+    #[must_use]
+    pub fn mention_html(&self) -> String {
+        format!(
+            "<a href=\"tg://user?id={}\">{}</a>",
+            self.id,
+            ParseMode::Html.escape(&self.full_name())
+        )
+    }
+
+    /// `@username` of the user, if the user has a username.
+    #[must_use]
+    pub fn username_mention(&self) -> Option<String> {
+        self.username
+            .as_ref()
+            .map(|username| format!("@{username}"))
+    }
+
+    /// First name followed by the last name, if the user has one.
+    #[must_use]
+    pub fn full_name(&self) -> String {
+        self.last_name.as_ref().map_or_else(
+            || self.first_name.clone(),
+            |last_name| format!("{} {last_name}", self.first_name),
+        )
+    }
+
+    /// `@username` if the user has a username, otherwise the [`full_name`](Self::full_name).
+    #[must_use]
+    pub fn display(&self) -> String {
+        self.username_mention().unwrap_or_else(|| self.full_name())
+    }
+
+    /// Whether the user has Telegram Premium. `false` when Telegram didn't tell.
+    #[must_use]
+    pub fn is_premium(&self) -> bool {
+        self.is_premium.unwrap_or(false)
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct Chat {
@@ -383,6 +553,8 @@ pub struct ChatFullInfo {
     pub join_by_request: Option<bool>,
     pub description: Option<String>,
     pub invite_link: Option<String>,
+    /// The most recent pinned message by sending date, see
+    /// [`pinned_message`](Self::pinned_message).
     pub pinned_message: Option<Box<Message>>,
     pub permissions: Option<ChatPermissions>,
     pub accepted_gift_types: AcceptedGiftTypes,
@@ -401,6 +573,27 @@ pub struct ChatFullInfo {
     pub location: Option<ChatLocation>,
 }
 
+impl ChatFullInfo {
+    /// `https://t.me/<username>` for public chats, otherwise the primary `invite_link` if the bot
+    /// may see it.
+    ///
+    /// `getChat` only returns the invite link to administrators with the right to invite users.
+    #[must_use]
+    pub fn public_url(&self) -> Option<String> {
+        self.username
+            .as_ref()
+            .map(|username| format!("https://t.me/{username}"))
+            .or_else(|| self.invite_link.clone())
+    }
+
+    /// The most recent pinned message by sending date, other pinned messages aren't returned
+    /// by `getChat`.
+    #[must_use]
+    pub fn pinned_message(&self) -> Option<&Message> {
+        self.pinned_message.as_deref()
+    }
+}
+
 #[apply(apistruct!)]
 pub struct Message {
     pub message_id: i32,
@@ -506,12 +699,462 @@ pub struct Message {
     pub reply_markup: Option<Box<InlineKeyboardMarkup>>,
 }
 
+impl Message {
+    /// Kind of the downloadable media attached to the message.
+    ///
+    /// Animations are also sent with a `document`, they are reported as [`MediaKind::Animation`].
+    #[must_use]
+    pub const fn media_kind(&self) -> Option<MediaKind> {
+        if self.animation.is_some() {
+            Some(MediaKind::Animation)
+        } else if self.audio.is_some() {
+            Some(MediaKind::Audio)
+        } else if self.document.is_some() {
+            Some(MediaKind::Document)
+        } else if self.photo.is_some() {
+            Some(MediaKind::Photo)
+        } else if self.sticker.is_some() {
+            Some(MediaKind::Sticker)
+        } else if self.video.is_some() {
+            Some(MediaKind::Video)
+        } else if self.video_note.is_some() {
+            Some(MediaKind::VideoNote)
+        } else if self.voice.is_some() {
+            Some(MediaKind::Voice)
+        } else {
+            None
+        }
+    }
+
+    /// `file_id` of the media attached to the message, see [`media_kind`](Self::media_kind).
+    ///
+    /// For photos this is the [`largest_photo`](Self::largest_photo).
+    #[must_use]
+    pub fn file_id(&self) -> Option<&str> {
+        let file_id = match self.media_kind()? {
+            MediaKind::Animation => &self.animation.as_ref()?.file_id,
+            MediaKind::Audio => &self.audio.as_ref()?.file_id,
+            MediaKind::Document => &self.document.as_ref()?.file_id,
+            MediaKind::Photo => &self.largest_photo()?.file_id,
+            MediaKind::Sticker => &self.sticker.as_ref()?.file_id,
+            MediaKind::Video => &self.video.as_ref()?.file_id,
+            MediaKind::VideoNote => &self.video_note.as_ref()?.file_id,
+            MediaKind::Voice => &self.voice.as_ref()?.file_id,
+        };
+        Some(file_id)
+    }
+
+    /// Biggest available size of the photo by resolution, then by file size.
+    #[must_use]
+    pub fn largest_photo(&self) -> Option<&PhotoSize> {
+        self.photo.as_ref()?.iter().max_by_key(|size| {
+            (
+                u64::from(size.width) * u64::from(size.height),
+                size.file_size.unwrap_or(0),
+            )
+        })
+    }
+
+    /// The `text` of a text message or the `caption` of a media message.
+    #[must_use]
+    pub fn text_or_caption(&self) -> Option<&str> {
+        self.text.as_deref().or(self.caption.as_deref())
+    }
+
+    /// Entities of [`text_or_caption`](Self::text_or_caption), taken from the same field.
+    #[must_use]
+    pub fn text_or_caption_entities(&self) -> Option<&[MessageEntity]> {
+        if self.text.is_some() {
+            self.entities.as_deref()
+        } else {
+            self.caption_entities.as_deref()
+        }
+    }
+
+    /// `https://t.me/<chat_username>/<message_id>` link to the message in a public chat.
+    #[must_use]
+    pub fn public_link(&self, chat_username: &str) -> String {
+        let chat_username = chat_username.trim_start_matches('@');
+        format!("https://t.me/{chat_username}/{}", self.message_id)
+    }
+
+    /// `https://t.me/c/<chat>/<message_id>` link to the message, only opening for chat members.
+    ///
+    /// The link uses the chat id without its `-100` prefix, so it only exists for supergroups and
+    /// channels. Returns `None` for private chats and basic groups.
+    #[must_use]
+    pub fn private_link(&self) -> Option<String> {
+        let chat = self
+            .chat
+            .id
+            .checked_neg()?
+            .checked_sub(1_000_000_000_000)
+            .filter(|chat| *chat > 0)?;
+        Some(format!("https://t.me/c/{chat}/{}", self.message_id))
+    }
+
+    /// Identifier of the message inside its chat, e.g. for `copyMessages` or `deleteMessages`.
+    #[must_use]
+    pub const fn id(&self) -> MessageId {
+        MessageId {
+            message_id: self.message_id,
+        }
+    }
+
+    /// Unique identifier of the message effect added to the message.
+    #[must_use]
+    pub fn effect_id(&self) -> Option<&str> {
+        self.effect_id.as_deref()
+    }
+
+    /// Connection to reply through when the message was received via a business account.
+    #[must_use]
+    pub fn business_connection_id(&self) -> Option<&str> {
+        self.business_connection_id.as_deref()
+    }
+
+    /// Whether the message was received or sent on behalf of a connected business account.
+    #[must_use]
+    pub const fn is_from_business(&self) -> bool {
+        self.business_connection_id.is_some()
+    }
+
+    /// Message in the same chat this message replies to.
+    #[must_use]
+    pub fn replied_message(&self) -> Option<&Self> {
+        self.reply_to_message.as_deref()
+    }
+
+    /// Quoted part of the replied message, if the reply quotes it.
+    #[must_use]
+    pub fn quote_text(&self) -> Option<&str> {
+        self.quote.as_deref().map(|quote| quote.text.as_str())
+    }
+
+    /// Whether the message replies to a message in this chat or in another chat or forum topic.
+    #[must_use]
+    pub const fn is_reply(&self) -> bool {
+        self.reply_to_message.is_some() || self.external_reply.is_some()
+    }
+
+    /// The new auto-delete time in seconds if this is a `message_auto_delete_timer_changed`
+    /// service message, 0 when the timer was turned off.
+    #[must_use]
+    pub fn auto_delete_timer(&self) -> Option<u32> {
+        self.message_auto_delete_timer_changed
+            .as_ref()
+            .map(|changed| changed.message_auto_delete_time)
+    }
+
+    /// Data sent by a Mini App launched from a keyboard button, e.g. a submitted form.
+    #[must_use]
+    pub fn web_app_data(&self) -> Option<&WebAppData> {
+        self.web_app_data.as_deref()
+    }
+
+    /// Set when the user allowed the bot to write messages, e.g. after a Mini App requested it.
+    #[must_use]
+    pub const fn write_access_allowed(&self) -> Option<&WriteAccessAllowed> {
+        self.write_access_allowed.as_ref()
+    }
+
+    /// Whether the message reports an event in the chat, e.g. a member joining or a pinned
+    /// message, instead of content sent by a user.
+    #[must_use]
+    pub fn is_service_message(&self) -> bool {
+        self.service_event().is_some()
+    }
+
+    /// The event reported by a service message, `None` for content sent by a user.
+    #[must_use]
+    pub fn service_event(&self) -> Option<ServiceEvent<'_>> {
+        self.chat_service_event()
+            .or_else(|| self.interaction_service_event())
+            .or_else(|| self.feature_service_event())
+    }
+
+    /// Changes of the chat itself, e.g. its members, title or pinned message.
+    fn chat_service_event(&self) -> Option<ServiceEvent<'_>> {
+        self.new_chat_members
+            .as_deref()
+            .map(ServiceEvent::NewChatMembers)
+            .or_else(|| {
+                self.left_chat_member
+                    .as_deref()
+                    .map(ServiceEvent::LeftChatMember)
+            })
+            .or_else(|| {
+                self.new_chat_title
+                    .as_deref()
+                    .map(ServiceEvent::NewChatTitle)
+            })
+            .or_else(|| {
+                self.new_chat_photo
+                    .as_deref()
+                    .map(ServiceEvent::NewChatPhoto)
+            })
+            .or_else(|| {
+                self.delete_chat_photo
+                    .map(|_| ServiceEvent::DeleteChatPhoto)
+            })
+            .or_else(|| {
+                self.group_chat_created
+                    .map(|_| ServiceEvent::GroupChatCreated)
+            })
+            .or_else(|| {
+                self.supergroup_chat_created
+                    .map(|_| ServiceEvent::SupergroupChatCreated)
+            })
+            .or_else(|| {
+                self.channel_chat_created
+                    .map(|_| ServiceEvent::ChannelChatCreated)
+            })
+            .or_else(|| {
+                self.message_auto_delete_timer_changed
+                    .as_deref()
+                    .map(ServiceEvent::AutoDeleteTimerChanged)
+            })
+            .or_else(|| self.migrate_to_chat_id.map(ServiceEvent::MigrateToChatId))
+            .or_else(|| {
+                self.migrate_from_chat_id
+                    .map(ServiceEvent::MigrateFromChatId)
+            })
+            .or_else(|| {
+                self.pinned_message
+                    .as_deref()
+                    .map(ServiceEvent::PinnedMessage)
+            })
+    }
+
+    /// Payments, shared data, gifts and other events involving the users of the chat.
+    fn interaction_service_event(&self) -> Option<ServiceEvent<'_>> {
+        self.successful_payment
+            .as_deref()
+            .map(ServiceEvent::SuccessfulPayment)
+            .or_else(|| {
+                self.refunded_payment
+                    .as_deref()
+                    .map(ServiceEvent::RefundedPayment)
+            })
+            .or_else(|| self.users_shared.as_deref().map(ServiceEvent::UsersShared))
+            .or_else(|| self.chat_shared.as_deref().map(ServiceEvent::ChatShared))
+            .or_else(|| self.gift.as_ref().map(ServiceEvent::Gift))
+            .or_else(|| self.unique_gift.as_ref().map(ServiceEvent::UniqueGift))
+            .or_else(|| {
+                self.connected_website
+                    .as_deref()
+                    .map(ServiceEvent::ConnectedWebsite)
+            })
+            .or_else(|| {
+                self.write_access_allowed
+                    .as_ref()
+                    .map(ServiceEvent::WriteAccessAllowed)
+            })
+            .or_else(|| {
+                self.passport_data
+                    .as_deref()
+                    .map(ServiceEvent::PassportData)
+            })
+            .or_else(|| {
+                self.proximity_alert_triggered
+                    .as_deref()
+                    .map(ServiceEvent::ProximityAlertTriggered)
+            })
+            .or_else(|| self.boost_added.as_deref().map(ServiceEvent::BoostAdded))
+            .or_else(|| {
+                self.chat_background_set
+                    .as_deref()
+                    .map(ServiceEvent::ChatBackgroundSet)
+            })
+            .or_else(|| {
+                self.checklist_tasks_done
+                    .as_deref()
+                    .map(ServiceEvent::ChecklistTasksDone)
+            })
+            .or_else(|| {
+                self.checklist_tasks_added
+                    .as_deref()
+                    .map(ServiceEvent::ChecklistTasksAdded)
+            })
+            .or_else(|| {
+                self.direct_message_price_changed
+                    .as_deref()
+                    .map(ServiceEvent::DirectMessagePriceChanged)
+            })
+    }
+
+    /// Events of forum topics, giveaways, suggested posts, video chats and Web Apps.
+    fn feature_service_event(&self) -> Option<ServiceEvent<'_>> {
+        self.forum_topic_created
+            .as_deref()
+            .map(ServiceEvent::ForumTopicCreated)
+            .or_else(|| {
+                self.forum_topic_edited
+                    .as_deref()
+                    .map(ServiceEvent::ForumTopicEdited)
+            })
+            .or_else(|| {
+                self.forum_topic_closed
+                    .as_deref()
+                    .map(ServiceEvent::ForumTopicClosed)
+            })
+            .or_else(|| {
+                self.forum_topic_reopened
+                    .as_deref()
+                    .map(ServiceEvent::ForumTopicReopened)
+            })
+            .or_else(|| {
+                self.general_forum_topic_hidden
+                    .as_deref()
+                    .map(ServiceEvent::GeneralForumTopicHidden)
+            })
+            .or_else(|| {
+                self.general_forum_topic_unhidden
+                    .as_deref()
+                    .map(ServiceEvent::GeneralForumTopicUnhidden)
+            })
+            .or_else(|| {
+                self.giveaway_created
+                    .as_ref()
+                    .map(ServiceEvent::GiveawayCreated)
+            })
+            .or_else(|| {
+                self.giveaway_completed
+                    .as_ref()
+                    .map(ServiceEvent::GiveawayCompleted)
+            })
+            .or_else(|| {
+                self.paid_message_price_changed
+                    .as_ref()
+                    .map(ServiceEvent::PaidMessagePriceChanged)
+            })
+            .or_else(|| {
+                self.suggested_post_approved
+                    .as_deref()
+                    .map(ServiceEvent::SuggestedPostApproved)
+            })
+            .or_else(|| {
+                self.suggested_post_approval_failed
+                    .as_deref()
+                    .map(ServiceEvent::SuggestedPostApprovalFailed)
+            })
+            .or_else(|| {
+                self.suggested_post_declined
+                    .as_deref()
+                    .map(ServiceEvent::SuggestedPostDeclined)
+            })
+            .or_else(|| {
+                self.suggested_post_paid
+                    .as_deref()
+                    .map(ServiceEvent::SuggestedPostPaid)
+            })
+            .or_else(|| {
+                self.suggested_post_refunded
+                    .as_deref()
+                    .map(ServiceEvent::SuggestedPostRefunded)
+            })
+            .or_else(|| {
+                self.video_chat_scheduled
+                    .as_deref()
+                    .map(ServiceEvent::VideoChatScheduled)
+            })
+            .or_else(|| {
+                self.video_chat_started
+                    .as_deref()
+                    .map(ServiceEvent::VideoChatStarted)
+            })
+            .or_else(|| {
+                self.video_chat_ended
+                    .as_deref()
+                    .map(ServiceEvent::VideoChatEnded)
+            })
+            .or_else(|| {
+                self.video_chat_participants_invited
+                    .as_deref()
+                    .map(ServiceEvent::VideoChatParticipantsInvited)
+            })
+            .or_else(|| self.web_app_data.as_deref().map(ServiceEvent::WebAppData))
+    }
+}
+
+/// Kind of media attached to a [`Message`], see [`Message::media_kind`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MediaKind {
+    Animation,
+    Audio,
+    Document,
+    Photo,
+    Sticker,
+    Video,
+    VideoNote,
+    Voice,
+}
+
+/// Event reported by a service message, see [`Message::service_event`].
+///
+/// Each variant borrows the corresponding field of the [`Message`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ServiceEvent<'a> {
+    NewChatMembers(&'a [User]),
+    LeftChatMember(&'a User),
+    NewChatTitle(&'a str),
+    NewChatPhoto(&'a [PhotoSize]),
+    DeleteChatPhoto,
+    GroupChatCreated,
+    SupergroupChatCreated,
+    ChannelChatCreated,
+    AutoDeleteTimerChanged(&'a MessageAutoDeleteTimerChanged),
+    MigrateToChatId(i64),
+    MigrateFromChatId(i64),
+    PinnedMessage(&'a MaybeInaccessibleMessage),
+    SuccessfulPayment(&'a SuccessfulPayment),
+    RefundedPayment(&'a RefundedPayment),
+    UsersShared(&'a UsersShared),
+    ChatShared(&'a ChatShared),
+    Gift(&'a GiftInfo),
+    UniqueGift(&'a UniqueGiftInfo),
+    ConnectedWebsite(&'a str),
+    WriteAccessAllowed(&'a WriteAccessAllowed),
+    PassportData(&'a PassportData),
+    ProximityAlertTriggered(&'a ProximityAlertTriggered),
+    BoostAdded(&'a ChatBoostAdded),
+    ChatBackgroundSet(&'a ChatBackground),
+    ChecklistTasksDone(&'a ChecklistTasksDone),
+    ChecklistTasksAdded(&'a ChecklistTasksAdded),
+    DirectMessagePriceChanged(&'a DirectMessagePriceChanged),
+    ForumTopicCreated(&'a ForumTopicCreated),
+    ForumTopicEdited(&'a ForumTopicEdited),
+    ForumTopicClosed(&'a ForumTopicClosed),
+    ForumTopicReopened(&'a ForumTopicReopened),
+    GeneralForumTopicHidden(&'a GeneralForumTopicHidden),
+    GeneralForumTopicUnhidden(&'a GeneralForumTopicUnhidden),
+    GiveawayCreated(&'a GiveawayCreated),
+    GiveawayCompleted(&'a GiveawayCompleted),
+    PaidMessagePriceChanged(&'a PaidMessagePriceChanged),
+    SuggestedPostApproved(&'a SuggestedPostApproved),
+    SuggestedPostApprovalFailed(&'a SuggestedPostApprovalFailed),
+    SuggestedPostDeclined(&'a SuggestedPostDeclined),
+    SuggestedPostPaid(&'a SuggestedPostPaid),
+    SuggestedPostRefunded(&'a SuggestedPostRefunded),
+    VideoChatScheduled(&'a VideoChatScheduled),
+    VideoChatStarted(&'a VideoChatStarted),
+    VideoChatEnded(&'a VideoChatEnded),
+    VideoChatParticipantsInvited(&'a VideoChatParticipantsInvited),
+    WebAppData(&'a WebAppData),
+}
+
 #[apply(apistruct!)]
 #[derive(Copy, Eq)]
 pub struct MessageId {
     pub message_id: i32,
 }
 
+impl From<&Message> for MessageId {
+    fn from(message: &Message) -> Self {
+        message.id()
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct MessageEntity {
@@ -525,6 +1168,113 @@ pub struct MessageEntity {
     pub custom_emoji_id: Option<String>,
 }
 
+/// Formats plain text with [`MessageEntity`]s instead of a [`ParseMode`], so nothing needs escaping.
+///
+/// Ranges are byte ranges of the text, e.g. from [`str::find`]. On [`build`](Self::build)
+/// they are converted to the UTF-16 offsets and lengths Telegram expects.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntityBuilder {
+    text: String,
+    marks: Vec<(Range<usize>, MessageEntity)>,
+}
+
+impl EntityBuilder {
+    #[must_use]
+    pub fn new<T: Into<String>>(text: T) -> Self {
+        Self {
+            text: text.into(),
+            marks: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn bold(self, range: Range<usize>) -> Self {
+        self.mark(range, MessageEntityType::Bold)
+    }
+
+    #[must_use]
+    pub fn italic(self, range: Range<usize>) -> Self {
+        self.mark(range, MessageEntityType::Italic)
+    }
+
+    #[must_use]
+    pub fn underline(self, range: Range<usize>) -> Self {
+        self.mark(range, MessageEntityType::Underline)
+    }
+
+    #[must_use]
+    pub fn strikethrough(self, range: Range<usize>) -> Self {
+        self.mark(range, MessageEntityType::Strikethrough)
+    }
+
+    #[must_use]
+    pub fn spoiler(self, range: Range<usize>) -> Self {
+        self.mark(range, MessageEntityType::Spoiler)
+    }
+
+    #[must_use]
+    pub fn code(self, range: Range<usize>) -> Self {
+        self.mark(range, MessageEntityType::Code)
+    }
+
+    #[must_use]
+    pub fn pre(mut self, range: Range<usize>, language: Option<String>) -> Self {
+        let mut entity = Self::entity(MessageEntityType::Pre);
+        entity.language = language;
+        self.marks.push((range, entity));
+        self
+    }
+
+    #[must_use]
+    pub fn link<U: Into<String>>(mut self, range: Range<usize>, url: U) -> Self {
+        let mut entity = Self::entity(MessageEntityType::TextLink);
+        entity.url = Some(url.into());
+        self.marks.push((range, entity));
+        self
+    }
+
+    /// The text and its entities ordered by offset.
+    ///
+    /// Fails when a range is out of bounds or not on a character boundary.
+    pub fn build(self) -> Result<(String, Vec<MessageEntity>), ValidationError> {
+        let mut entities = Vec::with_capacity(self.marks.len());
+        for (range, mut entity) in self.marks {
+            let (Some(before), Some(marked)) =
+                (self.text.get(..range.start), self.text.get(range.clone()))
+            else {
+                return Err(ValidationError::new(
+                    "entities",
+                    format!("{range:?} is not a valid range of the text"),
+                ));
+            };
+            let too_long =
+                || ValidationError::new("entities", "the text is too long for entity offsets");
+            entity.offset = u16::try_from(before.encode_utf16().count()).map_err(|_| too_long())?;
+            entity.length = u16::try_from(marked.encode_utf16().count()).map_err(|_| too_long())?;
+            entities.push(entity);
+        }
+        entities.sort_by_key(|entity| entity.offset);
+        Ok((self.text, entities))
+    }
+
+    fn mark(mut self, range: Range<usize>, type_field: MessageEntityType) -> Self {
+        self.marks.push((range, Self::entity(type_field)));
+        self
+    }
+
+    const fn entity(type_field: MessageEntityType) -> MessageEntity {
+        MessageEntity {
+            type_field,
+            offset: 0,
+            length: 0,
+            url: None,
+            user: None,
+            language: None,
+            custom_emoji_id: None,
+        }
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct TextQuote {
@@ -534,6 +1284,52 @@ pub struct TextQuote {
     pub is_manual: Option<bool>,
 }
 
+impl TextQuote {
+    /// Part of the quote `text` covered by `entity`.
+    ///
+    /// Entity offsets and lengths are in UTF-16 code units, so they can't be used to slice the
+    /// text directly. Returns `None` when the entity is out of bounds or splits a character.
+    #[must_use]
+    pub fn entity_text(&self, entity: &MessageEntity) -> Option<&str> {
+        utf16_slice(
+            &self.text,
+            usize::from(entity.offset),
+            usize::from(entity.length),
+        )
+    }
+
+    /// Quoted `text` sliced from the replied message's text or caption using `position`.
+    ///
+    /// Useful to check the quote still matches the original message, e.g. after an edit.
+    #[must_use]
+    pub fn source_range<'a>(&self, original_text: &'a str) -> Option<&'a str> {
+        let length = self.text.encode_utf16().count();
+        utf16_slice(original_text, usize::try_from(self.position).ok()?, length)
+    }
+}
+
+/// Slices `text` by an `offset` and `length` given in UTF-16 code units.
+fn utf16_slice(text: &str, offset: usize, length: usize) -> Option<&str> {
+    let end = offset.checked_add(length)?;
+    let mut start_byte = None;
+    let mut end_byte = None;
+    let mut units = 0;
+    let boundaries = text
+        .char_indices()
+        .chain(std::iter::once((text.len(), '\0')));
+    for (byte, character) in boundaries {
+        if units == offset {
+            start_byte = Some(byte);
+        }
+        if units == end {
+            end_byte = Some(byte);
+            break;
+        }
+        units += character.len_utf16();
+    }
+    text.get(start_byte?..end_byte?)
+}
+
 #[apply(apistruct!)]
 pub struct ExternalReplyInfo {
     pub origin: MessageOrigin,
@@ -756,6 +1552,68 @@ pub struct Dice {
     pub value: u8,
 }
 
+/// Symbol on a reel of the 🎰 slot machine, see [`Dice::slot_machine_reels`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SlotMachineSymbol {
+    Bar,
+    Grapes,
+    Lemon,
+    Seven,
+}
+
+impl Dice {
+    pub const BASKETBALL: &'static str = "🏀";
+    pub const BOWLING: &'static str = "🎳";
+    pub const DARTS: &'static str = "🎯";
+    pub const DICE: &'static str = "🎲";
+    pub const FOOTBALL: &'static str = "⚽";
+    pub const SLOT_MACHINE: &'static str = "🎰";
+
+    /// Symbols of the left, middle and right reel for a 🎰 dice.
+    ///
+    /// The value 1-64 minus one encodes one reel per two bits, starting with the left reel in
+    /// the lowest bits.
+    #[must_use]
+    pub fn slot_machine_reels(&self) -> Option<[SlotMachineSymbol; 3]> {
+        if self.emoji != Self::SLOT_MACHINE || !(1..=64).contains(&self.value) {
+            return None;
+        }
+        let combination = self.value - 1;
+        let reel = |index: u8| match (combination >> (index * 2)) & 0b11 {
+            0 => SlotMachineSymbol::Bar,
+            1 => SlotMachineSymbol::Grapes,
+            2 => SlotMachineSymbol::Lemon,
+            _ => SlotMachineSymbol::Seven,
+        };
+        Some([reel(0), reel(1), reel(2)])
+    }
+
+    /// Whether a 🎰 dice shows three sevens.
+    #[must_use]
+    pub fn is_jackpot(&self) -> bool {
+        self.slot_machine_reels() == Some([SlotMachineSymbol::Seven; 3])
+    }
+
+    /// Whether the dice shows the best result for its emoji.
+    ///
+    /// - 🎰: three identical symbols
+    /// - 🏀: the ball went in (4 or 5)
+    /// - ⚽: goal (3, 4 or 5)
+    /// - 🎲, 🎯, 🎳: the highest value 6, i.e. a bullseye or a strike
+    #[must_use]
+    pub fn is_win(&self) -> bool {
+        match self.emoji.as_str() {
+            Self::SLOT_MACHINE => self
+                .slot_machine_reels()
+                .is_some_and(|[left, middle, right]| left == middle && middle == right),
+            Self::BASKETBALL => matches!(self.value, 4 | 5),
+            Self::FOOTBALL => matches!(self.value, 3..=5),
+            Self::DICE | Self::DARTS | Self::BOWLING => self.value == 6,
+            _ => false,
+        }
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct PollOption {
@@ -772,6 +1630,23 @@ pub struct InputPollOption {
     pub text_entities: Option<Vec<MessageEntity>>,
 }
 
+impl InputPollOption {
+    /// Option with `text` formatted as [`ParseMode::MarkdownV2`].
+    #[must_use]
+    pub fn markdown<T: Into<String>>(text: T) -> Self {
+        Self::builder()
+            .text(text)
+            .text_parse_mode(ParseMode::MarkdownV2)
+            .build()
+    }
+
+    /// Option with `text` formatted by explicit `entities`, e.g. from an [`EntityBuilder`].
+    #[must_use]
+    pub fn with_entities<T: Into<String>>(text: T, entities: Vec<MessageEntity>) -> Self {
+        Self::builder().text(text).text_entities(entities).build()
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct PollAnswer {
@@ -801,6 +1676,49 @@ pub struct Poll {
     pub close_date: Option<u64>,
 }
 
+impl Poll {
+    /// Options with the most votes, several in case of a tie. Empty when nobody voted.
+    #[must_use]
+    pub fn winning_options(&self) -> Vec<&PollOption> {
+        let most_votes = self
+            .options
+            .iter()
+            .map(|option| option.voter_count)
+            .max()
+            .unwrap_or(0);
+        if most_votes == 0 {
+            return Vec::new();
+        }
+        self.options
+            .iter()
+            .filter(|option| option.voter_count == most_votes)
+            .collect()
+    }
+
+    /// Sum of the votes for all options.
+    ///
+    /// Differs from `total_voter_count` when multiple answers are allowed.
+    #[must_use]
+    pub fn total_votes(&self) -> u32 {
+        self.options.iter().map(|option| option.voter_count).sum()
+    }
+
+    /// Owned [`winning_options`](Self::winning_options) and `total_voter_count`.
+    #[must_use]
+    pub fn into_results(self) -> (Vec<PollOption>, u32) {
+        let most_votes = self
+            .winning_options()
+            .first()
+            .map(|option| option.voter_count);
+        let winners = self
+            .options
+            .into_iter()
+            .filter(|option| Some(option.voter_count) == most_votes)
+            .collect();
+        (winners, self.total_voter_count)
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct ChecklistTask {
@@ -1049,6 +1967,24 @@ pub struct File {
     pub file_path: Option<String>,
 }
 
+impl File {
+    /// Extension of the `file_path`, e.g. `jpg` for `photos/file_1.jpg`.
+    #[must_use]
+    pub fn file_extension(&self) -> Option<&str> {
+        let file_path = std::path::Path::new(self.file_path.as_deref()?);
+        file_path.extension().and_then(std::ffi::OsStr::to_str)
+    }
+
+    /// Name to save the download as: the `file_id` with the [`file_extension`](Self::file_extension).
+    #[must_use]
+    pub fn suggested_filename(&self) -> String {
+        self.file_extension().map_or_else(
+            || self.file_id.clone(),
+            |extension| format!("{}.{extension}", self.file_id),
+        )
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct ReplyKeyboardMarkup {
@@ -1246,6 +2182,50 @@ pub struct ChatPermissions {
     pub can_manage_topics: Option<bool>,
 }
 
+macro_rules! chat_permissions_setters {
+    ($($name:ident),+ $(,)?) => {
+        paste::paste! {
+            $(
+                #[doc = "Set `can_" $name "` while keeping the other permissions."]
+                #[must_use]
+                pub const fn [<allow_ $name>](mut self, value: bool) -> Self {
+                    self.[<can_ $name>] = Some(value);
+                    self
+                }
+            )+
+        }
+    };
+}
+
+impl ChatPermissions {
+    chat_permissions_setters!(
+        send_messages,
+        send_audios,
+        send_documents,
+        send_photos,
+        send_videos,
+        send_video_notes,
+        send_voice_notes,
+        send_polls,
+        send_other_messages,
+        add_web_page_previews,
+        change_info,
+        invite_users,
+        pin_messages,
+        manage_topics,
+    );
+
+    /// Derive a new permission set by changing some fields of this one.
+    ///
+    /// Useful together with the permissions of [`ChatFullInfo`] to only change what is needed
+    /// before calling `set_chat_permissions`.
+    #[must_use]
+    pub fn with<F: FnOnce(&mut Self)>(mut self, change: F) -> Self {
+        change(&mut self);
+        self
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct Birthdate {
@@ -1351,6 +2331,46 @@ pub struct StoryArea {
     pub type_field: StoryAreaType,
 }
 
+impl StoryArea {
+    /// Clickable area pointing to a location.
+    #[must_use]
+    pub const fn location(position: StoryAreaPosition, latitude: f64, longitude: f64) -> Self {
+        Self {
+            position,
+            type_field: StoryAreaType::Location(StoryAreaTypeLocation {
+                latitude,
+                longitude,
+                address: None,
+            }),
+        }
+    }
+
+    /// Area showing a suggested reaction.
+    #[must_use]
+    pub const fn suggested_reaction(
+        position: StoryAreaPosition,
+        reaction_type: ReactionType,
+    ) -> Self {
+        Self {
+            position,
+            type_field: StoryAreaType::SuggestedReaction(StoryAreaTypeSuggestedReaction {
+                reaction_type,
+                is_dark: None,
+                is_flipped: None,
+            }),
+        }
+    }
+
+    /// Clickable area pointing to an HTTP or `tg://` link.
+    #[must_use]
+    pub fn link<U: Into<String>>(position: StoryAreaPosition, url: U) -> Self {
+        Self {
+            position,
+            type_field: StoryAreaType::Link(StoryAreaTypeLink { url: url.into() }),
+        }
+    }
+}
+
 #[apply(apistruct!)]
 pub struct ChatLocation {
     pub location: Location,
@@ -1608,6 +2628,33 @@ pub enum ChatBoostSource {
     Giveaway(ChatBoostSourceGiveaway),
 }
 
+impl ChatBoostSource {
+    #[must_use]
+    pub const fn is_premium(&self) -> bool {
+        matches!(self, Self::Premium(_))
+    }
+
+    #[must_use]
+    pub const fn is_gift_code(&self) -> bool {
+        matches!(self, Self::GiftCode(_))
+    }
+
+    #[must_use]
+    pub const fn is_giveaway(&self) -> bool {
+        matches!(self, Self::Giveaway(_))
+    }
+
+    /// User that boosted the chat, if known.
+    #[must_use]
+    pub const fn user(&self) -> Option<&User> {
+        match self {
+            Self::Premium(premium) => Some(&premium.user),
+            Self::GiftCode(gift_code) => Some(&gift_code.user),
+            Self::Giveaway(giveaway) => giveaway.user.as_ref(),
+        }
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct ChatBoostSourcePremium {
@@ -1638,6 +2685,14 @@ pub struct ChatBoost {
     pub source: ChatBoostSource,
 }
 
+impl ChatBoost {
+    /// Whether the boost has not expired yet at the unix timestamp `now`.
+    #[must_use]
+    pub const fn is_active(&self, now: u64) -> bool {
+        self.expiration_date > now
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct ChatBoostUpdated {
@@ -1660,6 +2715,37 @@ pub struct UserChatBoosts {
     pub boosts: Vec<ChatBoost>,
 }
 
+impl UserChatBoosts {
+    #[must_use]
+    pub fn total_boosts(&self) -> usize {
+        self.boosts.len()
+    }
+
+    /// Boosts that have not expired yet at the unix timestamp `now`.
+    pub fn active_boosts(&self, now: u64) -> impl Iterator<Item = &ChatBoost> {
+        self.boosts.iter().filter(move |boost| boost.is_active(now))
+    }
+
+    /// Boosts obtained by subscribing to Telegram Premium.
+    pub fn premium_boosts(&self) -> impl Iterator<Item = &ChatBoost> {
+        self.boosts.iter().filter(|boost| boost.source.is_premium())
+    }
+
+    /// Boosts obtained by the creation of Telegram Premium gift codes.
+    pub fn gift_code_boosts(&self) -> impl Iterator<Item = &ChatBoost> {
+        self.boosts
+            .iter()
+            .filter(|boost| boost.source.is_gift_code())
+    }
+
+    /// Boosts obtained by giveaways.
+    pub fn giveaway_boosts(&self) -> impl Iterator<Item = &ChatBoost> {
+        self.boosts
+            .iter()
+            .filter(|boost| boost.source.is_giveaway())
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct BusinessConnection {
@@ -1690,6 +2776,49 @@ pub struct BusinessBotRights {
     pub can_manage_stories: Option<bool>,
 }
 
+macro_rules! business_connection_rights {
+    ($($name:ident),+ $(,)?) => {
+        paste::paste! {
+            $(
+                #[doc = "Whether the connection is enabled and the bot was granted `" $name "`."]
+                #[must_use]
+                pub fn $name(&self) -> bool {
+                    self.has_right(|rights| rights.$name)
+                }
+            )+
+        }
+    };
+}
+
+impl BusinessConnection {
+    business_connection_rights!(
+        can_reply,
+        can_read_messages,
+        can_delete_sent_messages,
+        can_delete_all_messages,
+        can_edit_name,
+        can_edit_bio,
+        can_edit_profile_photo,
+        can_edit_username,
+        can_change_gift_settings,
+        can_view_gifts_and_stars,
+        can_convert_gifts_to_stars,
+        can_transfer_and_upgrade_gifts,
+        can_transfer_stars,
+        can_manage_stories,
+    );
+
+    /// Whether the bot can both see the Star balance and transfer Stars of the business account.
+    #[must_use]
+    pub fn can_manage_stars(&self) -> bool {
+        self.can_view_gifts_and_stars() && self.can_transfer_stars()
+    }
+
+    fn has_right(&self, right: impl FnOnce(&BusinessBotRights) -> Option<bool>) -> bool {
+        self.is_enabled && self.rights.as_ref().and_then(right).unwrap_or(false)
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct BusinessMessagesDeleted {
@@ -1733,6 +2862,798 @@ mod serde_tests {
         assert!(matches!(member, ChatMember::Kicked(_)));
     }
 
+    #[test]
+    pub fn bot_command_scope_helpers_serialize() {
+        use crate::test_json::assert_json_str;
+
+        assert_json_str(&BotCommandScope::default(), r#"{"type":"default"}"#);
+        assert_json_str(
+            &BotCommandScope::all_private_chats(),
+            r#"{"type":"all_private_chats"}"#,
+        );
+        assert_json_str(
+            &BotCommandScope::all_group_chats(),
+            r#"{"type":"all_group_chats"}"#,
+        );
+        assert_json_str(
+            &BotCommandScope::all_chat_administrators(),
+            r#"{"type":"all_chat_administrators"}"#,
+        );
+        assert_json_str(&BotCommandScope::chat(1), r#"{"type":"chat","chat_id":1}"#);
+        assert_json_str(
+            &BotCommandScope::chat_administrators("@channel".to_string()),
+            r#"{"type":"chat_administrators","chat_id":"@channel"}"#,
+        );
+        assert_json_str(
+            &BotCommandScope::chat_member(1, 2),
+            r#"{"type":"chat_member","chat_id":1,"user_id":2}"#,
+        );
+    }
+
+    #[test]
+    pub fn chat_permissions_toggle_keeps_other_fields() {
+        let current = ChatPermissions::builder()
+            .can_send_messages(true)
+            .can_send_polls(false)
+            .can_pin_messages(true)
+            .build();
+
+        let updated = current.allow_send_polls(true);
+        assert_eq!(updated.can_send_polls, Some(true));
+        assert_eq!(updated.can_send_messages, Some(true));
+        assert_eq!(updated.can_pin_messages, Some(true));
+        assert_eq!(updated.can_change_info, None);
+
+        let updated = current.with(|permissions| permissions.can_send_messages = Some(false));
+        assert_eq!(
+            updated,
+            ChatPermissions {
+                can_send_messages: Some(false),
+                ..current
+            }
+        );
+    }
+
+    #[test]
+    pub fn user_chat_boosts_are_classified() {
+        fn ids<'a>(boosts: Vec<&'a ChatBoost>) -> Vec<&'a str> {
+            boosts
+                .into_iter()
+                .map(|boost| boost.boost_id.as_str())
+                .collect()
+        }
+
+        let boosts_content = r#"{
+            "boosts": [
+                {
+                    "boost_id": "1",
+                    "add_date": 100,
+                    "expiration_date": 1000,
+                    "source": {
+                        "source": "premium",
+                        "user": {"id": 1, "is_bot": false, "first_name": "Premium"}
+                    }
+                },
+                {
+                    "boost_id": "2",
+                    "add_date": 100,
+                    "expiration_date": 500,
+                    "source": {
+                        "source": "gift_code",
+                        "user": {"id": 2, "is_bot": false, "first_name": "Gift"}
+                    }
+                },
+                {
+                    "boost_id": "3",
+                    "add_date": 100,
+                    "expiration_date": 2000,
+                    "source": {
+                        "source": "giveaway",
+                        "giveaway_message_id": 7,
+                        "is_unclaimed": true
+                    }
+                }
+            ]
+        }"#;
+
+        let boosts: UserChatBoosts = serde_json::from_str(boosts_content).unwrap();
+        assert_eq!(boosts.total_boosts(), 3);
+
+        assert_eq!(ids(boosts.premium_boosts().collect()), ["1"]);
+        assert_eq!(ids(boosts.gift_code_boosts().collect()), ["2"]);
+        assert_eq!(ids(boosts.giveaway_boosts().collect()), ["3"]);
+        assert_eq!(ids(boosts.active_boosts(500).collect()), ["1", "3"]);
+        assert_eq!(
+            ids(boosts.active_boosts(2000).collect()),
+            Vec::<&str>::new()
+        );
+
+        assert_eq!(boosts.boosts[1].source.user().map(|user| user.id), Some(2));
+        assert_eq!(boosts.boosts[2].source.user(), None);
+    }
+
+    #[test]
+    pub fn business_message_accessors() {
+        let message_content = r#"{
+            "message_id": 1,
+            "date": 1700000000,
+            "business_connection_id": "connection",
+            "sender_business_bot": {"id": 2, "is_bot": true, "first_name": "Bot"},
+            "chat": {"id": 3, "type": "private", "first_name": "Customer"},
+            "effect_id": "5104841245755180586",
+            "text": "Hello"
+        }"#;
+
+        let message: Message = serde_json::from_str(message_content).unwrap();
+        assert!(message.is_from_business());
+        assert_eq!(message.business_connection_id(), Some("connection"));
+        assert_eq!(message.effect_id(), Some("5104841245755180586"));
+
+        let message = Message {
+            business_connection_id: None,
+            effect_id: None,
+            ..message
+        };
+        assert!(!message.is_from_business());
+        assert_eq!(message.business_connection_id(), None);
+        assert_eq!(message.effect_id(), None);
+    }
+
+    #[test]
+    pub fn reply_with_manual_quote() {
+        let message_content = r#"{
+            "message_id": 2,
+            "date": 1700000001,
+            "chat": {"id": 3, "type": "private", "first_name": "Customer"},
+            "reply_to_message": {
+                "message_id": 1,
+                "date": 1700000000,
+                "chat": {"id": 3, "type": "private", "first_name": "Customer"},
+                "text": "Hi 👋 see the bold part"
+            },
+            "quote": {
+                "text": "👋 see the bold",
+                "entities": [{"type": "bold", "offset": 7, "length": 8}],
+                "position": 3,
+                "is_manual": true
+            },
+            "text": "Which part?"
+        }"#;
+
+        let message: Message = serde_json::from_str(message_content).unwrap();
+        assert!(message.is_reply());
+        let replied = message.replied_message().unwrap();
+        assert_eq!(replied.message_id, 1);
+        assert!(!replied.is_reply());
+        assert_eq!(message.quote_text(), Some("👋 see the bold"));
+
+        let quote = message.quote.as_deref().unwrap();
+        let entity = &quote.entities.as_ref().unwrap()[0];
+        assert_eq!(quote.entity_text(entity), Some("the bold"));
+        assert_eq!(
+            quote.source_range(replied.text.as_deref().unwrap()),
+            Some("👋 see the bold")
+        );
+
+        let split_emoji = MessageEntity {
+            offset: 1,
+            ..entity.clone()
+        };
+        assert_eq!(quote.entity_text(&split_emoji), None);
+        let out_of_bounds = MessageEntity {
+            length: 100,
+            ..entity.clone()
+        };
+        assert_eq!(quote.entity_text(&out_of_bounds), None);
+    }
+
+    #[test]
+    pub fn business_connection_with_partial_rights() {
+        let connection_content = r#"{
+            "id": "connection",
+            "user": {"id": 1, "is_bot": false, "first_name": "Owner"},
+            "user_chat_id": 1,
+            "date": 1700000000,
+            "rights": {
+                "can_reply": true,
+                "can_read_messages": false,
+                "can_view_gifts_and_stars": true
+            },
+            "is_enabled": true
+        }"#;
+
+        let connection: BusinessConnection = serde_json::from_str(connection_content).unwrap();
+        assert!(connection.can_reply());
+        assert!(!connection.can_read_messages());
+        assert!(connection.can_view_gifts_and_stars());
+        assert!(!connection.can_transfer_stars());
+        assert!(!connection.can_manage_stars());
+        assert!(!connection.can_manage_stories());
+
+        let disabled = BusinessConnection {
+            is_enabled: false,
+            ..connection.clone()
+        };
+        assert!(!disabled.can_reply());
+
+        let no_rights = BusinessConnection {
+            rights: None,
+            ..connection
+        };
+        assert!(!no_rights.can_reply());
+    }
+
+    #[test]
+    pub fn message_id_from_message() {
+        let message_content = r#"{
+            "message_id": 2746,
+            "date": 1700000000,
+            "chat": {"id": 3, "type": "private", "first_name": "User"},
+            "text": "Hello"
+        }"#;
+
+        let message: Message = serde_json::from_str(message_content).unwrap();
+        let id = message.id();
+        assert_eq!(id, MessageId::from(&message));
+        assert_eq!(id.message_id, message.message_id);
+        crate::test_json::assert_json_str(&id, r#"{"message_id":2746}"#);
+    }
+
+    #[test]
+    pub fn poll_results() {
+        let poll_content = r#"{
+            "id": "poll",
+            "question": "Toppings?",
+            "options": [
+                {"text": "Cheese", "voter_count": 4},
+                {"text": "Olives", "voter_count": 2},
+                {"text": "Ham", "voter_count": 4}
+            ],
+            "total_voter_count": 5,
+            "is_closed": true,
+            "is_anonymous": false,
+            "type": "regular",
+            "allows_multiple_answers": true
+        }"#;
+
+        let poll: Poll = serde_json::from_str(poll_content).unwrap();
+        let winners: Vec<&str> = poll
+            .winning_options()
+            .iter()
+            .map(|option| option.text.as_str())
+            .collect();
+        assert_eq!(winners, ["Cheese", "Ham"]);
+        assert_eq!(poll.total_votes(), 10);
+
+        let unanswered = Poll {
+            options: vec![PollOption {
+                text: "Cheese".to_string(),
+                text_entities: None,
+                voter_count: 0,
+            }],
+            ..poll
+        };
+        assert!(unanswered.winning_options().is_empty());
+        assert!(unanswered.into_results().0.is_empty());
+    }
+
+    #[test]
+    pub fn menu_button_variants_json() {
+        crate::test_json::assert_json_str(&MenuButton::commands(), r#"{"type":"commands"}"#);
+        crate::test_json::assert_json_str(&MenuButton::Default, r#"{"type":"default"}"#);
+        crate::test_json::assert_json_str(
+            &MenuButton::web_app("Open app", "https://example.com/app"),
+            r#"{"type":"web_app","text":"Open app","web_app":{"url":"https://example.com/app"}}"#,
+        );
+
+        let button: MenuButton = serde_json::from_str(
+            r#"{"type":"web_app","text":"Open app","web_app":{"url":"https://example.com/app"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            button,
+            MenuButton::web_app("Open app", "https://example.com/app")
+        );
+    }
+
+    #[test]
+    pub fn chat_administrators_owner_and_admins() {
+        let administrators_content = r#"[
+            {
+                "status": "administrator",
+                "user": {"id": 2, "is_bot": false, "first_name": "Admin"},
+                "can_be_edited": false,
+                "is_anonymous": false,
+                "can_manage_chat": true,
+                "can_delete_messages": true,
+                "can_manage_video_chats": true,
+                "can_restrict_members": true,
+                "can_promote_members": false,
+                "can_change_info": true,
+                "can_invite_users": true,
+                "can_post_stories": false,
+                "can_edit_stories": false,
+                "can_delete_stories": false
+            },
+            {
+                "status": "creator",
+                "user": {"id": 1, "is_bot": false, "first_name": "Owner"},
+                "is_anonymous": false
+            },
+            {
+                "status": "administrator",
+                "user": {"id": 3, "is_bot": true, "first_name": "Bot"},
+                "can_be_edited": true,
+                "is_anonymous": false,
+                "can_manage_chat": true,
+                "can_delete_messages": false,
+                "can_manage_video_chats": false,
+                "can_restrict_members": false,
+                "can_promote_members": false,
+                "can_change_info": false,
+                "can_invite_users": true,
+                "can_post_stories": false,
+                "can_edit_stories": false,
+                "can_delete_stories": false
+            }
+        ]"#;
+
+        let administrators: Vec<ChatMember> = serde_json::from_str(administrators_content).unwrap();
+        assert_eq!(administrators.owner().map(|owner| owner.user().id), Some(1));
+        let admin_ids: Vec<u64> = administrators
+            .admins_excluding_owner()
+            .iter()
+            .map(|admin| admin.user().id)
+            .collect();
+        assert_eq!(admin_ids, [2, 3]);
+        assert!(administrators.has_admin(1));
+        assert!(administrators.has_admin(3));
+        assert!(!administrators.has_admin(4));
+    }
+
+    #[test]
+    pub fn message_media_file_id() {
+        let message_content = r#"{
+            "message_id": 1,
+            "date": 1700000000,
+            "chat": {"id": 3, "type": "private", "first_name": "User"},
+            "photo": [
+                {"file_id": "small", "file_unique_id": "s", "width": 90, "height": 60, "file_size": 1000},
+                {"file_id": "large", "file_unique_id": "l", "width": 1280, "height": 853, "file_size": 90000},
+                {"file_id": "medium", "file_unique_id": "m", "width": 320, "height": 213, "file_size": 12000}
+            ]
+        }"#;
+
+        let message: Message = serde_json::from_str(message_content).unwrap();
+        assert_eq!(message.media_kind(), Some(MediaKind::Photo));
+        assert_eq!(message.file_id(), Some("large"));
+
+        let message_content = r#"{
+            "message_id": 2,
+            "date": 1700000000,
+            "chat": {"id": 3, "type": "private", "first_name": "User"},
+            "document": {"file_id": "document", "file_unique_id": "d", "file_name": "report.pdf"}
+        }"#;
+
+        let message: Message = serde_json::from_str(message_content).unwrap();
+        assert_eq!(message.media_kind(), Some(MediaKind::Document));
+        assert_eq!(message.file_id(), Some("document"));
+
+        let message = Message {
+            document: None,
+            ..message
+        };
+        assert_eq!(message.media_kind(), None);
+        assert_eq!(message.file_id(), None);
+    }
+
+    #[test]
+    pub fn file_extension_from_file_path() {
+        let file = File {
+            file_id: "AgACAgIAAxk".to_string(),
+            file_unique_id: "unique".to_string(),
+            file_size: Some(1024),
+            file_path: Some("photos/file_1.jpg".to_string()),
+        };
+        assert_eq!(file.file_extension(), Some("jpg"));
+        assert_eq!(file.suggested_filename(), "AgACAgIAAxk.jpg");
+
+        let file = File {
+            file_path: Some("documents/file_2".to_string()),
+            ..file
+        };
+        assert_eq!(file.file_extension(), None);
+        assert_eq!(file.suggested_filename(), "AgACAgIAAxk");
+
+        let file = File {
+            file_path: None,
+            ..file
+        };
+        assert_eq!(file.file_extension(), None);
+    }
+
+    #[test]
+    pub fn dice_values_are_interpreted() {
+        let dice = |emoji: &str, value: u8| Dice {
+            emoji: emoji.to_string(),
+            value,
+        };
+
+        let jackpot = dice(Dice::SLOT_MACHINE, 64);
+        assert!(jackpot.is_jackpot());
+        assert!(jackpot.is_win());
+
+        let lemons = dice(Dice::SLOT_MACHINE, 43);
+        assert_eq!(
+            lemons.slot_machine_reels(),
+            Some([SlotMachineSymbol::Lemon; 3])
+        );
+        assert!(lemons.is_win());
+        assert!(!lemons.is_jackpot());
+
+        let mixed = dice(Dice::SLOT_MACHINE, 2);
+        assert_eq!(
+            mixed.slot_machine_reels(),
+            Some([
+                SlotMachineSymbol::Grapes,
+                SlotMachineSymbol::Bar,
+                SlotMachineSymbol::Bar
+            ])
+        );
+        assert!(!mixed.is_win());
+
+        assert!(dice(Dice::DARTS, 6).is_win());
+        assert!(!dice(Dice::DARTS, 5).is_win());
+        assert!(!dice(Dice::DARTS, 6).is_jackpot());
+        assert!(dice(Dice::BASKETBALL, 4).is_win());
+        assert!(!dice(Dice::BASKETBALL, 3).is_win());
+        assert!(dice(Dice::FOOTBALL, 3).is_win());
+    }
+
+    #[test]
+    pub fn entity_builder_uses_utf16_offsets() {
+        let text = "Hi 👋 bold and link";
+        let bold = text.find("bold").unwrap();
+        let link = text.find("link").unwrap();
+        let (built_text, entities) = EntityBuilder::new(text)
+            .link(link..link + 4, "https://example.com")
+            .bold(bold..bold + 4)
+            .build()
+            .unwrap();
+
+        assert_eq!(built_text, text);
+        assert_eq!(entities.len(), 2);
+        // 👋 is two UTF-16 code units but four bytes
+        assert_eq!(entities[0].type_field, MessageEntityType::Bold);
+        assert_eq!((entities[0].offset, entities[0].length), (6, 4));
+        assert_eq!(entities[1].type_field, MessageEntityType::TextLink);
+        assert_eq!((entities[1].offset, entities[1].length), (15, 4));
+        assert_eq!(entities[1].url.as_deref(), Some("https://example.com"));
+
+        let quote = TextQuote {
+            text: built_text,
+            entities: Some(entities.clone()),
+            position: 0,
+            is_manual: None,
+        };
+        assert_eq!(quote.entity_text(&entities[0]), Some("bold"));
+
+        assert!(EntityBuilder::new(text).bold(4..6).build().is_err());
+        assert!(EntityBuilder::new(text).bold(0..100).build().is_err());
+    }
+
+    #[test]
+    pub fn user_display_names() {
+        let user = User::builder()
+            .id(1)
+            .is_bot(false)
+            .first_name("Jane")
+            .build();
+        assert_eq!(user.full_name(), "Jane");
+        assert_eq!(user.display(), "Jane");
+        assert!(!user.is_premium());
+
+        let user = User {
+            last_name: Some("Doe".to_string()),
+            is_premium: Some(true),
+            ..user
+        };
+        assert_eq!(user.full_name(), "Jane Doe");
+        assert_eq!(user.display(), "Jane Doe");
+        assert!(user.is_premium());
+
+        let user = User {
+            username: Some("janedoe".to_string()),
+            ..user
+        };
+        assert_eq!(user.full_name(), "Jane Doe");
+        assert_eq!(user.display(), "@janedoe");
+    }
+
+    #[test]
+    fn formatted_poll_options() {
+        let option = InputPollOption::markdown("*bold* choice");
+        assert_eq!(option.text_parse_mode, Some(ParseMode::MarkdownV2));
+        crate::test_json::assert_json_str(
+            &option,
+            r#"{"text":"*bold* choice","text_parse_mode":"MarkdownV2"}"#,
+        );
+
+        let entity = MessageEntity::builder()
+            .type_field(MessageEntityType::Bold)
+            .offset(0)
+            .length(4)
+            .build();
+        let option = InputPollOption::with_entities("bold choice", vec![entity.clone()]);
+        assert_eq!(option.text_parse_mode, None);
+        assert_eq!(option.text_entities, Some(vec![entity]));
+    }
+
+    #[test]
+    fn chat_full_info_pinned_message() {
+        let json = r#"{
+            "id": -100,
+            "type": "supergroup",
+            "title": "Group",
+            "accent_color_id": 0,
+            "max_reaction_count": 11,
+            "accepted_gift_types": {
+                "unlimited_gifts": false,
+                "limited_gifts": false,
+                "unique_gifts": false,
+                "premium_subscription": false
+            },
+            "pinned_message": {
+                "message_id": 42,
+                "date": 1700000000,
+                "chat": {"id": -100, "type": "supergroup", "title": "Group"},
+                "text": "Read the rules"
+            }
+        }"#;
+        let chat: ChatFullInfo = serde_json::from_str(json).unwrap();
+        let pinned = chat.pinned_message().unwrap();
+        assert_eq!(pinned.message_id, 42);
+        assert_eq!(pinned.text.as_deref(), Some("Read the rules"));
+
+        let chat = ChatFullInfo {
+            pinned_message: None,
+            ..chat
+        };
+        assert_eq!(chat.pinned_message(), None);
+    }
+
+    #[test]
+    fn message_links() {
+        let json = r#"{
+            "message_id": 42,
+            "date": 1700000000,
+            "chat": {"id": -1001234567890, "type": "supergroup", "title": "Group"},
+            "text": "hello"
+        }"#;
+        let message: Message = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            message.private_link().as_deref(),
+            Some("https://t.me/c/1234567890/42")
+        );
+        assert_eq!(message.public_link("@group"), "https://t.me/group/42");
+        assert_eq!(message.public_link("group"), "https://t.me/group/42");
+
+        let basic_group = Message {
+            chat: Box::new(Chat {
+                id: -123456,
+                ..*message.chat.clone()
+            }),
+            ..message.clone()
+        };
+        assert_eq!(basic_group.private_link(), None);
+
+        let private = Message {
+            chat: Box::new(Chat {
+                id: 1234,
+                ..*message.chat.clone()
+            }),
+            ..message
+        };
+        assert_eq!(private.private_link(), None);
+    }
+
+    #[test]
+    fn chat_full_info_public_url() {
+        let json = r#"{
+            "id": -1001234567890,
+            "type": "channel",
+            "title": "News",
+            "username": "news",
+            "invite_link": "https://t.me/+invite",
+            "accepted_gift_types": {
+                "unlimited_gifts": false,
+                "limited_gifts": false,
+                "unique_gifts": false,
+                "premium_subscription": false
+            }
+        }"#;
+        let channel: ChatFullInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(channel.public_url().as_deref(), Some("https://t.me/news"));
+
+        let private_group = ChatFullInfo {
+            type_field: ChatType::Supergroup,
+            username: None,
+            ..channel
+        };
+        assert_eq!(
+            private_group.public_url().as_deref(),
+            Some("https://t.me/+invite")
+        );
+
+        let without_invite_link = ChatFullInfo {
+            invite_link: None,
+            ..private_group
+        };
+        assert_eq!(without_invite_link.public_url(), None);
+    }
+
+    #[test]
+    fn text_message_text_or_caption() {
+        let json = r#"{
+            "message_id": 5,
+            "date": 1700000000,
+            "chat": {"id": 1, "type": "private", "first_name": "Jane"},
+            "text": "/start now",
+            "entities": [{"type": "bot_command", "offset": 0, "length": 6}]
+        }"#;
+        let message: Message = serde_json::from_str(json).unwrap();
+        assert_eq!(message.text_or_caption(), Some("/start now"));
+        let entities = message.text_or_caption_entities().unwrap();
+        assert_eq!(entities.len(), 1);
+        assert_eq!(entities[0].type_field, MessageEntityType::BotCommand);
+    }
+
+    #[test]
+    fn captioned_photo_text_or_caption() {
+        let json = r#"{
+            "message_id": 6,
+            "date": 1700000000,
+            "chat": {"id": 1, "type": "private", "first_name": "Jane"},
+            "photo": [{"file_id": "a", "file_unique_id": "b", "width": 90, "height": 90}],
+            "caption": "look #cats",
+            "caption_entities": [{"type": "hashtag", "offset": 5, "length": 5}]
+        }"#;
+        let message: Message = serde_json::from_str(json).unwrap();
+        assert_eq!(message.text_or_caption(), Some("look #cats"));
+        let entities = message.text_or_caption_entities().unwrap();
+        assert_eq!(entities.len(), 1);
+        assert_eq!(entities[0].type_field, MessageEntityType::Hashtag);
+
+        let photo = Message {
+            caption: None,
+            caption_entities: None,
+            ..message
+        };
+        assert_eq!(photo.text_or_caption(), None);
+        assert_eq!(photo.text_or_caption_entities(), None);
+    }
+
+    #[test]
+    fn auto_delete_timer_changed_is_a_service_message() {
+        let json = r#"{
+            "message_id": 7,
+            "date": 1700000000,
+            "chat": {"id": -100, "type": "supergroup", "title": "Group"},
+            "from": {"id": 1, "is_bot": false, "first_name": "Admin"},
+            "message_auto_delete_timer_changed": {"message_auto_delete_time": 86400}
+        }"#;
+        let message: Message = serde_json::from_str(json).unwrap();
+        assert_eq!(message.auto_delete_timer(), Some(86_400));
+        assert!(message.is_service_message());
+
+        let text = Message {
+            message_auto_delete_timer_changed: None,
+            text: Some("hello".to_string()),
+            ..message
+        };
+        assert_eq!(text.auto_delete_timer(), None);
+        assert!(!text.is_service_message());
+    }
+
+    #[test]
+    fn web_app_data_message() {
+        let json = r#"{
+            "message_id": 9,
+            "date": 1700000000,
+            "chat": {"id": 1, "type": "private", "first_name": "Jane"},
+            "from": {"id": 1, "is_bot": false, "first_name": "Jane"},
+            "web_app_data": {"data": "{\"size\":\"L\"}", "button_text": "Order"}
+        }"#;
+        let message: Message = serde_json::from_str(json).unwrap();
+        let web_app_data = message.web_app_data().unwrap();
+        assert_eq!(web_app_data.data, r#"{"size":"L"}"#);
+        assert_eq!(web_app_data.button_text, "Order");
+        assert_eq!(message.write_access_allowed(), None);
+
+        let json = r#"{
+            "message_id": 10,
+            "date": 1700000000,
+            "chat": {"id": 1, "type": "private", "first_name": "Jane"},
+            "write_access_allowed": {"web_app_name": "shop"}
+        }"#;
+        let message: Message = serde_json::from_str(json).unwrap();
+        let allowed = message.write_access_allowed().unwrap();
+        assert_eq!(allowed.web_app_name.as_deref(), Some("shop"));
+        assert_eq!(message.web_app_data(), None);
+    }
+
+    #[test]
+    fn service_events() {
+        let json = r#"{
+            "message_id": 8,
+            "date": 1700000000,
+            "chat": {"id": -100, "type": "supergroup", "title": "Group"},
+            "from": {"id": 1, "is_bot": false, "first_name": "Admin"},
+            "new_chat_members": [{"id": 2, "is_bot": false, "first_name": "Newbie"}]
+        }"#;
+        let message: Message = serde_json::from_str(json).unwrap();
+        match message.service_event() {
+            Some(ServiceEvent::NewChatMembers(members)) => {
+                assert_eq!(members.len(), 1);
+                assert_eq!(members[0].first_name, "Newbie");
+            }
+            other => panic!("new chat members expected: {other:?}"),
+        }
+
+        let json = r#"{
+            "message_id": 9,
+            "date": 1700000000,
+            "chat": {"id": -100, "type": "supergroup", "title": "Group"},
+            "pinned_message": {
+                "message_id": 5,
+                "date": 1699999999,
+                "chat": {"id": -100, "type": "supergroup", "title": "Group"},
+                "text": "Rules"
+            }
+        }"#;
+        let message: Message = serde_json::from_str(json).unwrap();
+        match message.service_event() {
+            Some(ServiceEvent::PinnedMessage(MaybeInaccessibleMessage::Message(pinned))) => {
+                assert_eq!(pinned.text.as_deref(), Some("Rules"));
+            }
+            other => panic!("pinned message expected: {other:?}"),
+        }
+
+        let text = Message {
+            pinned_message: None,
+            text: Some("hello".to_string()),
+            ..message
+        };
+        assert_eq!(text.service_event(), None);
+    }
+
+    #[test]
+    pub fn user_mentions_are_escaped() {
+        let user = User::builder()
+            .id(123)
+            .is_bot(false)
+            .first_name("John_Doe*")
+            .last_name("<Jr.>")
+            .build();
+        assert_eq!(
+            user.mention_markdown(),
+            r"[John\_Doe\* <Jr\.\>](tg://user?id=123)"
+        );
+        assert_eq!(
+            user.mention_html(),
+            r#"<a href="tg://user?id=123">John_Doe* &lt;Jr.&gt;</a>"#
+        );
+        assert_eq!(user.username_mention(), None);
+
+        let user = User {
+            last_name: None,
+            username: Some("johndoe".to_string()),
+            ..user
+        };
+        assert_eq!(user.mention_markdown(), r"[John\_Doe\*](tg://user?id=123)");
+        assert_eq!(user.username_mention().as_deref(), Some("@johndoe"));
+    }
+
     #[test]
     pub fn unknown_entity_kind_is_parsed() {
         let entity_content = r#"{
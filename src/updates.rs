@@ -7,8 +7,8 @@ use crate::macros::{apistruct, apply};
 use crate::payments::{PaidMediaPurchased, PreCheckoutQuery, ShippingQuery};
 use crate::types::{
     AllowedUpdate, BusinessConnection, BusinessMessagesDeleted, CallbackQuery, ChatBoostRemoved,
-    ChatBoostUpdated, ChatJoinRequest, ChatMemberUpdated, Message, MessageReactionCountUpdated,
-    MessageReactionUpdated, Poll, PollAnswer,
+    ChatBoostUpdated, ChatJoinRequest, ChatMemberUpdated, MaybeInaccessibleMessage, Message,
+    MessageReactionCountUpdated, MessageReactionUpdated, Poll, PollAnswer, User,
 };
 
 /// Represents an incoming update from telegram.
@@ -51,6 +51,77 @@ pub enum UpdateContent {
     PurchasedPaidMedia(PaidMediaPurchased),
 }
 
+impl Update {
+    /// Parse an update, e.g. from the body of a webhook request.
+    ///
+    /// Uses `simd_json` when the `simd-json` feature is enabled.
+    #[cfg(any(feature = "client-reqwest", feature = "client-ureq"))]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, crate::Error> {
+        crate::json::decode_slice(bytes)
+    }
+
+    /// Whether the update carries a service message, see [`Message::service_event`].
+    #[must_use]
+    pub fn is_service(&self) -> bool {
+        match &self.content {
+            UpdateContent::Message(message)
+            | UpdateContent::EditedMessage(message)
+            | UpdateContent::ChannelPost(message)
+            | UpdateContent::EditedChannelPost(message)
+            | UpdateContent::BusinessMessage(message)
+            | UpdateContent::EditedBusinessMessage(message) => message.is_service_message(),
+            _ => false,
+        }
+    }
+
+    /// Unix timestamp of the event behind the update, when Telegram provides one.
+    ///
+    /// Precedence:
+    /// - edited messages and posts: `edit_date`, falling back to `date`
+    /// - other messages, reactions, chat member updates, join requests, business connections:
+    ///   their `date`
+    /// - chat boosts: `add_date` of the boost, removed boosts: `remove_date`
+    /// - callback queries: `date` of the message the button is attached to
+    ///
+    /// `None` for inline queries, payments, polls, deleted business messages and callback
+    /// queries of inline or inaccessible messages.
+    #[must_use]
+    pub fn timestamp(&self) -> Option<u64> {
+        match &self.content {
+            UpdateContent::Message(message)
+            | UpdateContent::ChannelPost(message)
+            | UpdateContent::BusinessMessage(message) => Some(message.date),
+            UpdateContent::EditedMessage(message)
+            | UpdateContent::EditedChannelPost(message)
+            | UpdateContent::EditedBusinessMessage(message) => {
+                Some(message.edit_date.unwrap_or(message.date))
+            }
+            UpdateContent::BusinessConnection(connection) => Some(connection.date),
+            UpdateContent::MessageReaction(reaction) => Some(reaction.date),
+            UpdateContent::MessageReactionCount(reaction_count) => Some(reaction_count.date),
+            UpdateContent::MyChatMember(member) | UpdateContent::ChatMember(member) => {
+                Some(member.date)
+            }
+            UpdateContent::ChatJoinRequest(request) => Some(request.date),
+            UpdateContent::ChatBoost(boost) => Some(boost.boost.add_date),
+            UpdateContent::RemovedChatBoost(removed) => Some(removed.remove_date),
+            UpdateContent::CallbackQuery(query) => match &query.message {
+                Some(MaybeInaccessibleMessage::Message(message)) => Some(message.date),
+                // The date of inaccessible messages is always 0
+                Some(MaybeInaccessibleMessage::InaccessibleMessage(_)) | None => None,
+            },
+            UpdateContent::DeletedBusinessMessages(_)
+            | UpdateContent::InlineQuery(_)
+            | UpdateContent::ChosenInlineResult(_)
+            | UpdateContent::ShippingQuery(_)
+            | UpdateContent::PreCheckoutQuery(_)
+            | UpdateContent::Poll(_)
+            | UpdateContent::PollAnswer(_)
+            | UpdateContent::PurchasedPaidMedia(_) => None,
+        }
+    }
+}
+
 #[apply(apistruct!)]
 #[derive(Eq)]
 pub struct WebhookInfo {
@@ -65,6 +136,50 @@ pub struct WebhookInfo {
     pub allowed_updates: Option<Vec<AllowedUpdate>>,
 }
 
+impl WebhookInfo {
+    /// Whether more than `threshold` updates are waiting to be delivered.
+    #[must_use]
+    pub const fn is_backlogged(&self, threshold: u32) -> bool {
+        self.pending_update_count > threshold
+    }
+
+    /// Whether the last delivery error happened at most `within_secs` seconds before `now`.
+    ///
+    /// `now` is a unix timestamp like `last_error_date`.
+    #[must_use]
+    pub const fn has_recent_error(&self, now: u64, within_secs: u64) -> bool {
+        match self.last_error_date {
+            Some(date) => now.saturating_sub(date) <= within_secs,
+            None => false,
+        }
+    }
+}
+
+/// Result of a readiness probe, see `health_check` on the API extension traits.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HealthStatus {
+    /// Username of the bot, proving that the token works.
+    pub username: Option<String>,
+    /// URL of the webhook, `None` when updates are received with `getUpdates`.
+    pub webhook_url: Option<String>,
+    pub pending_update_count: u32,
+    pub last_error_date: Option<u64>,
+    pub last_error_message: Option<String>,
+}
+
+impl HealthStatus {
+    #[must_use]
+    pub fn new(me: User, webhook_info: WebhookInfo) -> Self {
+        Self {
+            username: me.username,
+            webhook_url: Some(webhook_info.url).filter(|url| !url.is_empty()),
+            pending_update_count: webhook_info.pending_update_count,
+            last_error_date: webhook_info.last_error_date,
+            last_error_message: webhook_info.last_error_message,
+        }
+    }
+}
+
 #[cfg(test)]
 mod serde_tests {
     use super::*;
@@ -126,4 +241,108 @@ mod serde_tests {
 
         assert_eq!(update, expected);
     }
+
+    #[test]
+    pub fn webhook_info_alert_helpers() {
+        let info_content = r#"{
+            "url": "https://example.com/hook",
+            "has_custom_certificate": false,
+            "pending_update_count": 120,
+            "last_error_date": 1700000000,
+            "last_error_message": "Connection timed out"
+        }"#;
+        let info: WebhookInfo = serde_json::from_str(info_content).unwrap();
+
+        assert!(info.is_backlogged(100));
+        assert!(!info.is_backlogged(120));
+        assert!(info.has_recent_error(1700000060, 300));
+        assert!(!info.has_recent_error(1700000600, 300));
+
+        let healthy = WebhookInfo {
+            pending_update_count: 0,
+            last_error_date: None,
+            last_error_message: None,
+            ..info
+        };
+        assert!(!healthy.is_backlogged(0));
+        assert!(!healthy.has_recent_error(1700000060, 300));
+    }
+
+    #[test]
+    pub fn timestamp_of_message_updates() {
+        let update: Update = serde_json::from_str(
+            r#"{
+                "update_id": 1,
+                "message": {
+                    "message_id": 1,
+                    "date": 1700000000,
+                    "chat": {"id": 1, "type": "private", "first_name": "User"},
+                    "text": "Hello"
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(update.timestamp(), Some(1_700_000_000));
+
+        let update: Update = serde_json::from_str(
+            r#"{
+                "update_id": 2,
+                "edited_message": {
+                    "message_id": 1,
+                    "date": 1700000000,
+                    "edit_date": 1700000060,
+                    "chat": {"id": 1, "type": "private", "first_name": "User"},
+                    "text": "Hello!"
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(update.timestamp(), Some(1_700_000_060));
+
+        let update: Update = serde_json::from_str(
+            r#"{
+                "update_id": 3,
+                "callback_query": {
+                    "id": "query",
+                    "from": {"id": 1, "is_bot": false, "first_name": "User"},
+                    "chat_instance": "instance",
+                    "inline_message_id": "inline",
+                    "data": "data"
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(update.timestamp(), None);
+    }
+
+    #[test]
+    pub fn service_message_updates() {
+        let update: Update = serde_json::from_str(
+            r#"{
+                "update_id": 1,
+                "message": {
+                    "message_id": 1,
+                    "date": 1700000000,
+                    "chat": {"id": -100, "type": "group", "title": "Group"},
+                    "left_chat_member": {"id": 2, "is_bot": false, "first_name": "User"}
+                }
+            }"#,
+        )
+        .unwrap();
+        assert!(update.is_service());
+
+        let update: Update = serde_json::from_str(
+            r#"{
+                "update_id": 2,
+                "message": {
+                    "message_id": 2,
+                    "date": 1700000000,
+                    "chat": {"id": -100, "type": "group", "title": "Group"},
+                    "text": "Hello"
+                }
+            }"#,
+        )
+        .unwrap();
+        assert!(!update.is_service());
+    }
 }